@@ -0,0 +1,285 @@
+//! RFC 7838 Alt-Svc field values
+//!
+//! A server advertises alternative services, such as HTTP/3 on another port,
+//! with the `alt-svc` response header or an ALTSVC frame
+//! (`H2Event::AltSvc`). Both carry the same field value, for example
+//! `h3=":443"; ma=3600, h2="alt.example.com:8443"`, or `clear` to withdraw
+//! earlier advertisements.
+//!
+//! ```rust
+//! use h2_sans_io::AltSvc;
+//!
+//! let value = AltSvc::parse(br#"h3=":443"; ma=3600, h2="alt.example.com:8443""#).unwrap();
+//! let AltSvc::Services(services) = value else { unreachable!() };
+//! assert_eq!(services[0].protocol_id, "h3");
+//! assert_eq!(services[0].port, 443);
+//! assert_eq!(services[0].max_age, 3600);
+//! assert_eq!(services[1].host, "alt.example.com");
+//! ```
+
+/// Freshness lifetime of an alternative without an `ma` parameter, in
+/// seconds (RFC 7838 Section 3.1).
+pub const DEFAULT_MAX_AGE: u64 = 86400;
+
+/// A parsed Alt-Svc field value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AltSvc {
+    /// `clear`: forget all alternatives previously advertised for the origin.
+    Clear,
+    /// Alternatives in the server's order of preference.
+    Services(Vec<AltService>),
+}
+
+/// One alternative service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AltService {
+    /// ALPN protocol ID, e.g. `h3`, with percent-encoding removed.
+    pub protocol_id: String,
+    /// Host of the alternative; empty means the origin's own host.
+    pub host: String,
+    pub port: u16,
+    /// Seconds the alternative stays fresh (`ma`, default [`DEFAULT_MAX_AGE`]).
+    pub max_age: u64,
+    /// `persist=1`: keep the alternative across network changes.
+    pub persist: bool,
+    /// Parameters other than `ma` and `persist`, in field order.
+    pub params: Vec<(String, String)>,
+}
+
+impl AltService {
+    /// An alternative with default `ma` and no other parameters.
+    pub fn new(protocol_id: impl Into<String>, host: impl Into<String>, port: u16) -> Self {
+        Self {
+            protocol_id: protocol_id.into(),
+            host: host.into(),
+            port,
+            max_age: DEFAULT_MAX_AGE,
+            persist: false,
+            params: Vec::new(),
+        }
+    }
+}
+
+impl AltSvc {
+    /// Parse an Alt-Svc field value.
+    ///
+    /// Returns `None` if the value does not match the RFC 7838 Section 3
+    /// grammar; the advertisement should then be ignored. `ma` values that are
+    /// not a number of seconds and `persist` values other than `1` are
+    /// ignored, as are empty list elements.
+    pub fn parse(value: &[u8]) -> Option<Self> {
+        let mut p = Parser { input: value, pos: 0 };
+        p.skip_ows();
+        let start = p.pos;
+        if p.token().as_deref() == Some("clear") {
+            p.skip_ows();
+            if p.at_end() {
+                return Some(AltSvc::Clear);
+            }
+        }
+        p.pos = start;
+
+        let mut services = Vec::new();
+        loop {
+            p.skip_ows();
+            if p.eat(b',') {
+                continue; // empty list element
+            }
+            if p.at_end() {
+                break;
+            }
+            services.push(p.alt_value()?);
+            p.skip_ows();
+            if !p.at_end() && !p.eat(b',') {
+                return None;
+            }
+        }
+        if services.is_empty() {
+            return None;
+        }
+        Some(AltSvc::Services(services))
+    }
+
+    /// Serialize as an Alt-Svc field value, omitting `ma` when it is the
+    /// default and `persist` when false.
+    pub fn to_field_value(&self) -> String {
+        let services = match self {
+            AltSvc::Clear => return "clear".to_string(),
+            AltSvc::Services(services) => services,
+        };
+        let mut out = String::new();
+        for (i, service) in services.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            for b in service.protocol_id.bytes() {
+                if is_tchar(b) && b != b'%' {
+                    out.push(b as char);
+                } else {
+                    out.push_str(&format!("%{:02X}", b));
+                }
+            }
+            out.push('=');
+            push_quoted(&mut out, &format!("{}:{}", service.host, service.port));
+            if service.max_age != DEFAULT_MAX_AGE {
+                out.push_str(&format!("; ma={}", service.max_age));
+            }
+            if service.persist {
+                out.push_str("; persist=1");
+            }
+            for (name, value) in &service.params {
+                out.push_str("; ");
+                out.push_str(name);
+                out.push('=');
+                if !value.is_empty() && value.bytes().all(is_tchar) {
+                    out.push_str(value);
+                } else {
+                    push_quoted(&mut out, value);
+                }
+            }
+        }
+        out
+    }
+}
+
+impl std::fmt::Display for AltSvc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_field_value())
+    }
+}
+
+/// RFC 9110 Section 5.6.2 token characters.
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+fn push_quoted(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn at_end(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.input.get(self.pos) == Some(&b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_ows(&mut self) {
+        while matches!(self.input.get(self.pos), Some(b' ' | b'\t')) {
+            self.pos += 1;
+        }
+    }
+
+    fn token(&mut self) -> Option<String> {
+        let start = self.pos;
+        while self.input.get(self.pos).is_some_and(|&b| is_tchar(b)) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return None;
+        }
+        Some(String::from_utf8(self.input[start..self.pos].to_vec()).expect("tchars are ASCII"))
+    }
+
+    fn quoted_string(&mut self) -> Option<String> {
+        if !self.eat(b'"') {
+            return None;
+        }
+        let mut value = Vec::new();
+        loop {
+            match *self.input.get(self.pos)? {
+                b'"' => {
+                    self.pos += 1;
+                    return String::from_utf8(value).ok();
+                }
+                b'\\' => {
+                    value.push(*self.input.get(self.pos + 1)?);
+                    self.pos += 2;
+                }
+                b => {
+                    value.push(b);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// alt-value = alternative *( OWS ";" OWS parameter )
+    fn alt_value(&mut self) -> Option<AltService> {
+        let protocol_id = percent_decode(&self.token()?)?;
+        if !self.eat(b'=') {
+            return None;
+        }
+        let authority = self.quoted_string()?;
+        let (host, port) = authority.rsplit_once(':')?;
+        if port.is_empty() || !port.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let mut service = AltService::new(protocol_id, host, port.parse().ok()?);
+
+        loop {
+            let before = self.pos;
+            self.skip_ows();
+            if !self.eat(b';') {
+                self.pos = before;
+                return Some(service);
+            }
+            self.skip_ows();
+            let name = self.token()?.to_ascii_lowercase();
+            if !self.eat(b'=') {
+                return None;
+            }
+            let value = if self.input.get(self.pos) == Some(&b'"') {
+                self.quoted_string()?
+            } else {
+                self.token()?
+            };
+            match name.as_str() {
+                "ma" => {
+                    if let Ok(max_age) = value.parse() {
+                        service.max_age = max_age;
+                    }
+                }
+                "persist" => service.persist = value == "1",
+                _ => service.params.push((name, value)),
+            }
+        }
+    }
+}
+
+/// Decode a percent-encoded protocol-id (RFC 7838 Section 3).
+fn percent_decode(token: &str) -> Option<String> {
+    let bytes = token.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3).filter(|h| h.iter().all(u8::is_ascii_hexdigit))?;
+            out.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
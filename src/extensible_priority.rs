@@ -0,0 +1,422 @@
+//! RFC 9218 Extensible Priorities
+//!
+//! Clients signal a stream's priority with the `priority` header and, after
+//! the request, with PRIORITY_UPDATE frames (`H2Event::PriorityUpdate`). Both
+//! carry a Structured Field dictionary (RFC 8941) such as `u=1, i`:
+//! `u` is the urgency, 0 (highest) to 7, default 3; `i` marks a response
+//! that is useful when delivered incrementally.
+//!
+//! [`ExtensiblePriority::parse`] reads the field value and
+//! [`UrgencyScheduler`] uses the result to pick the stream whose queued DATA
+//! goes out next. Endpoints that only use these priorities can say so with
+//! `SETTINGS_NO_RFC7540_PRIORITIES` (see `H2Codec::create_settings_frame`).
+//!
+//! ```rust
+//! use h2_sans_io::{ExtensiblePriority, UrgencyScheduler};
+//!
+//! let mut scheduler = UrgencyScheduler::new();
+//! scheduler.open(1, ExtensiblePriority::parse(b"u=5"));
+//! scheduler.open(3, ExtensiblePriority::parse(b"u=1, i"));
+//! scheduler.set_ready(1, true);
+//! scheduler.set_ready(3, true);
+//! assert_eq!(scheduler.next_stream(), Some(3));
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+
+/// Urgency of a stream without a priority signal (RFC 9218 Section 4.1).
+pub const DEFAULT_URGENCY: u8 = 3;
+
+/// Streams not yet opened, known only from PRIORITY_UPDATE frames, kept by an
+/// [`UrgencyScheduler`] by default.
+pub const DEFAULT_MAX_UNOPENED_STREAMS: usize = 16;
+
+/// Lowest urgency (highest value) defined by RFC 9218.
+pub const MAX_URGENCY: u8 = 7;
+
+/// Priority parameters from a `priority` header or PRIORITY_UPDATE frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtensiblePriority {
+    /// 0 (most urgent) to 7.
+    pub urgency: u8,
+    /// True if the response can be used as it arrives, so it may share
+    /// bandwidth with other incremental responses of the same urgency.
+    pub incremental: bool,
+}
+
+impl Default for ExtensiblePriority {
+    fn default() -> Self {
+        Self { urgency: DEFAULT_URGENCY, incremental: false }
+    }
+}
+
+impl ExtensiblePriority {
+    /// Parse a Priority Field Value.
+    ///
+    /// Returns `None` if the value is not a valid Structured Field dictionary;
+    /// treat that like an absent signal (use the default). Unknown parameters
+    /// and out-of-range or mistyped `u` and `i` values are ignored, leaving the
+    /// default for that parameter (RFC 9218 Section 4).
+    pub fn parse(value: &[u8]) -> Option<Self> {
+        let mut priority = Self::default();
+        for (key, item) in parse_dictionary(value)? {
+            match (key.as_slice(), item) {
+                (b"u", Item::Integer(u)) if (0..=i64::from(MAX_URGENCY)).contains(&u) => priority.urgency = u as u8,
+                (b"i", Item::Boolean(i)) => priority.incremental = i,
+                _ => {}
+            }
+        }
+        Some(priority)
+    }
+
+    /// Serialize as a Priority Field Value, omitting default parameters.
+    /// The default priority serializes to an empty string.
+    pub fn to_field_value(&self) -> String {
+        let mut parts = Vec::new();
+        if self.urgency != DEFAULT_URGENCY {
+            parts.push(format!("u={}", self.urgency));
+        }
+        if self.incremental {
+            parts.push("i".to_string());
+        }
+        parts.join(", ")
+    }
+}
+
+impl std::fmt::Display for ExtensiblePriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_field_value())
+    }
+}
+
+/// Dictionary member values that matter for priorities; everything else
+/// parses but is not kept.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Item {
+    Integer(i64),
+    Boolean(bool),
+    Other,
+}
+
+/// Parse an RFC 8941 dictionary into (key, value) pairs; later duplicates
+/// override earlier ones when applied in order.
+fn parse_dictionary(input: &[u8]) -> Option<Vec<(Vec<u8>, Item)>> {
+    let mut p = Parser { input, pos: 0 };
+    p.skip(|b| b == b' ');
+    let mut members = Vec::new();
+    if p.at_end_trimmed() {
+        return Some(members);
+    }
+    loop {
+        let key = p.key()?;
+        let item = if p.eat(b'=') {
+            if p.peek() == Some(b'(') {
+                p.inner_list()?;
+                Item::Other
+            } else {
+                p.bare_item()?
+            }
+        } else {
+            Item::Boolean(true)
+        };
+        p.parameters()?;
+        members.push((key, item));
+
+        p.skip(|b| b == b' ' || b == b'\t');
+        if p.at_end_trimmed() {
+            return Some(members);
+        }
+        if !p.eat(b',') {
+            return None;
+        }
+        p.skip(|b| b == b' ' || b == b'\t');
+        p.peek()?; // a trailing comma is invalid
+    }
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        let found = self.peek() == Some(b);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn skip(&mut self, pred: impl Fn(u8) -> bool) {
+        while self.peek().is_some_and(&pred) {
+            self.pos += 1;
+        }
+    }
+
+    /// True if only trailing spaces remain.
+    fn at_end_trimmed(&self) -> bool {
+        self.input[self.pos..].iter().all(|&b| b == b' ')
+    }
+
+    fn key(&mut self) -> Option<Vec<u8>> {
+        let start = self.pos;
+        if !self.peek().is_some_and(|b| b.is_ascii_lowercase() || b == b'*') {
+            return None;
+        }
+        self.skip(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'_' | b'-' | b'.' | b'*'));
+        Some(self.input[start..self.pos].to_vec())
+    }
+
+    fn parameters(&mut self) -> Option<()> {
+        while self.eat(b';') {
+            self.skip(|b| b == b' ');
+            self.key()?;
+            if self.eat(b'=') {
+                self.bare_item()?;
+            }
+        }
+        Some(())
+    }
+
+    fn inner_list(&mut self) -> Option<()> {
+        self.eat(b'(');
+        loop {
+            self.skip(|b| b == b' ');
+            if self.eat(b')') {
+                return self.parameters();
+            }
+            self.bare_item()?;
+            self.parameters()?;
+            if !matches!(self.peek(), Some(b' ') | Some(b')')) {
+                return None;
+            }
+        }
+    }
+
+    fn bare_item(&mut self) -> Option<Item> {
+        match self.peek()? {
+            b'-' | b'0'..=b'9' => self.number(),
+            b'"' => self.string(),
+            b':' => self.byte_sequence(),
+            b'?' => {
+                self.pos += 1;
+                let value = match self.peek()? {
+                    b'0' => false,
+                    b'1' => true,
+                    _ => return None,
+                };
+                self.pos += 1;
+                Some(Item::Boolean(value))
+            }
+            b if b.is_ascii_alphabetic() || b == b'*' => {
+                self.pos += 1;
+                self.skip(|b| b.is_ascii_graphic() && !matches!(b, b'"' | b'(' | b')' | b',' | b';' | b'=' | b'[' | b']' | b'{' | b'}' | b'<' | b'>' | b'@' | b'\\' | b'?'));
+                Some(Item::Other)
+            }
+            _ => None,
+        }
+    }
+
+    fn number(&mut self) -> Option<Item> {
+        let negative = self.eat(b'-');
+        let start = self.pos;
+        self.skip(|b| b.is_ascii_digit());
+        let int_len = self.pos - start;
+        if int_len == 0 {
+            return None;
+        }
+        if self.eat(b'.') {
+            let frac_start = self.pos;
+            self.skip(|b| b.is_ascii_digit());
+            let frac_len = self.pos - frac_start;
+            return (int_len <= 12 && (1..=3).contains(&frac_len)).then_some(Item::Other);
+        }
+        if int_len > 15 {
+            return None;
+        }
+        let digits = std::str::from_utf8(&self.input[start..self.pos]).ok()?;
+        let value: i64 = digits.parse().ok()?;
+        Some(Item::Integer(if negative { -value } else { value }))
+    }
+
+    fn string(&mut self) -> Option<Item> {
+        self.pos += 1;
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return Some(Item::Other);
+                }
+                b'\\' => {
+                    self.pos += 1;
+                    if !matches!(self.peek()?, b'"' | b'\\') {
+                        return None;
+                    }
+                    self.pos += 1;
+                }
+                0x20..=0x7E => self.pos += 1,
+                _ => return None,
+            }
+        }
+    }
+
+    fn byte_sequence(&mut self) -> Option<Item> {
+        self.pos += 1;
+        self.skip(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='));
+        self.eat(b':').then_some(Item::Other)
+    }
+}
+
+/// Per-stream state in an [`UrgencyScheduler`].
+#[derive(Debug, Clone)]
+struct Entry {
+    priority: ExtensiblePriority,
+    /// Set by a PRIORITY_UPDATE, which takes precedence over the header.
+    updated: bool,
+    ready: bool,
+    /// Pick counter value when the stream was last chosen.
+    last_served: u64,
+}
+
+/// Send scheduler for RFC 9218 priorities.
+///
+/// Streams with data ready are served in urgency order. Within an urgency
+/// level, non-incremental streams go first, one at a time in stream ID order;
+/// incremental streams then take turns frame by frame (RFC 9218 Section 10).
+///
+/// PRIORITY_UPDATE frames may arrive for streams not yet opened; their
+/// priority is kept until the stream is opened. At most
+/// [`UrgencyScheduler::max_unopened_streams`] such streams are retained, the
+/// oldest being dropped first (RFC 9218 Section 7), so a peer cannot grow the
+/// scheduler without bound.
+#[derive(Debug, Clone)]
+pub struct UrgencyScheduler {
+    streams: HashMap<u32, Entry>,
+    picks: u64,
+    /// Streams added by `update` and not opened yet, oldest first
+    unopened: VecDeque<u32>,
+    max_unopened: usize,
+}
+
+impl Default for UrgencyScheduler {
+    fn default() -> Self {
+        Self {
+            streams: HashMap::new(),
+            picks: 0,
+            unopened: VecDeque::new(),
+            max_unopened: DEFAULT_MAX_UNOPENED_STREAMS,
+        }
+    }
+}
+
+impl UrgencyScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how many streams not yet opened, known only from PRIORITY_UPDATE
+    /// frames, are retained (default [`DEFAULT_MAX_UNOPENED_STREAMS`]).
+    /// Excess streams are dropped, oldest first.
+    pub fn set_max_unopened_streams(&mut self, max: usize) {
+        self.max_unopened = max;
+        self.evict_unopened();
+    }
+
+    /// Number of streams not yet opened that are retained.
+    pub fn max_unopened_streams(&self) -> usize {
+        self.max_unopened
+    }
+
+    /// Add a stream with the priority from its `priority` header, if any
+    /// (parse it with [`ExtensiblePriority::parse`]). A priority already set
+    /// by a PRIORITY_UPDATE frame is kept.
+    pub fn open(&mut self, stream_id: u32, priority: Option<ExtensiblePriority>) {
+        let entry = self.streams.entry(stream_id).or_insert(Entry {
+            priority: ExtensiblePriority::default(),
+            updated: false,
+            ready: false,
+            last_served: 0,
+        });
+        if !entry.updated {
+            entry.priority = priority.unwrap_or_default();
+        }
+        self.unopened.retain(|&id| id != stream_id);
+    }
+
+    /// Apply a PRIORITY_UPDATE frame. The stream is added if it is not
+    /// open yet, dropping the oldest stream not yet opened if that exceeds
+    /// [`UrgencyScheduler::max_unopened_streams`].
+    pub fn update(&mut self, stream_id: u32, priority: ExtensiblePriority) {
+        if let Some(entry) = self.streams.get_mut(&stream_id) {
+            entry.priority = priority;
+            entry.updated = true;
+            return;
+        }
+        self.streams.insert(stream_id, Entry {
+            priority,
+            updated: true,
+            ready: false,
+            last_served: 0,
+        });
+        self.unopened.push_back(stream_id);
+        self.evict_unopened();
+    }
+
+    /// The stream's current priority.
+    pub fn priority(&self, stream_id: u32) -> Option<ExtensiblePriority> {
+        self.streams.get(&stream_id).map(|e| e.priority)
+    }
+
+    /// Record whether a stream has DATA queued and may send it.
+    pub fn set_ready(&mut self, stream_id: u32, ready: bool) {
+        if let Some(entry) = self.streams.get_mut(&stream_id) {
+            entry.ready = ready;
+        }
+    }
+
+    pub fn is_ready(&self, stream_id: u32) -> bool {
+        self.streams.get(&stream_id).is_some_and(|e| e.ready)
+    }
+
+    /// Pick the stream that should send the next DATA frame, or `None` if no
+    /// stream is ready. Call once per frame.
+    pub fn next_stream(&mut self) -> Option<u32> {
+        let (&stream_id, _) = self.streams.iter().filter(|(_, e)| e.ready).min_by_key(|(&id, e)| {
+            let turn = if e.priority.incremental { e.last_served } else { 0 };
+            (e.priority.urgency, e.priority.incremental, turn, id)
+        })?;
+        self.picks += 1;
+        self.streams.get_mut(&stream_id).expect("picked from map").last_served = self.picks;
+        Some(stream_id)
+    }
+
+    /// Forget a stream once it is closed.
+    pub fn remove(&mut self, stream_id: u32) {
+        self.streams.remove(&stream_id);
+        self.unopened.retain(|&id| id != stream_id);
+    }
+
+    pub fn contains(&self, stream_id: u32) -> bool {
+        self.streams.contains_key(&stream_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.streams.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.streams.is_empty()
+    }
+
+    fn evict_unopened(&mut self) {
+        while self.unopened.len() > self.max_unopened {
+            let oldest = self.unopened.pop_front().expect("non-empty");
+            self.streams.remove(&oldest);
+        }
+    }
+}
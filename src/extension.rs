@@ -0,0 +1,154 @@
+//! Extension frame handlers
+//!
+//! HTTP/2 lets endpoints define new frame types (RFC 9113 Section 5.5). The
+//! codec parses the extensions it knows (ALTSVC, ORIGIN, PRIORITY_UPDATE) and
+//! ignores other unknown types. Register an [`ExtensionHandler`] with
+//! `H2Codec::register_extension` to parse a frame type yourself: the codec
+//! hands it each frame of that type and reports what it returns as
+//! `H2Event::Extension`, carrying an [`ExtensionEvent`] that downcasts back to
+//! the handler's own type.
+//!
+//! ```rust
+//! use h2_sans_io::{ExtensionEvent, H2Codec, H2Event, H2FrameHeader};
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Ping2(Vec<u8>);
+//!
+//! let mut codec = H2Codec::new();
+//! codec.set_preface_received(true);
+//! codec.register_extension(0xf0, |_: &H2FrameHeader, payload: &[u8]| {
+//!     Ok(Some(ExtensionEvent::new(Ping2(payload.to_vec()))))
+//! });
+//!
+//! let events = codec.process(&[0, 0, 2, 0xf0, 0, 0, 0, 0, 0, 1, 2]).unwrap();
+//! let H2Event::Extension { event, .. } = &events[0] else { unreachable!() };
+//! assert_eq!(event.downcast_ref::<Ping2>(), Some(&Ping2(vec![1, 2])));
+//! ```
+
+use std::any::Any;
+use std::collections::BTreeMap;
+
+use crate::h2_codec::H2FrameHeader;
+
+/// Parses frames of one extension type.
+///
+/// Implemented for closures taking the frame header and payload, for handlers
+/// that only need [`ExtensionHandler::parse`].
+pub trait ExtensionHandler: Send {
+    /// Parse one frame. `Ok(Some(event))` is reported as
+    /// `H2Event::Extension`, `Ok(None)` drops the frame, and `Err` is a
+    /// connection error returned from `H2Codec::process`.
+    fn parse(&mut self, header: &H2FrameHeader, payload: &[u8]) -> Result<Option<ExtensionEvent>, String>;
+
+    /// Whether this frame may arrive while a header block waits for its
+    /// CONTINUATION frames on `pending_stream`.
+    ///
+    /// RFC 9113 Section 6.10 allows nothing but CONTINUATION there, so the
+    /// default is `false` and the frame is a connection error like any other.
+    /// Return `true` only for extensions whose negotiation says otherwise.
+    fn allowed_in_header_block(&self, header: &H2FrameHeader, pending_stream: u32) -> bool {
+        let _ = (header, pending_stream);
+        false
+    }
+}
+
+impl<F> ExtensionHandler for F
+where
+    F: FnMut(&H2FrameHeader, &[u8]) -> Result<Option<ExtensionEvent>, String> + Send,
+{
+    fn parse(&mut self, header: &H2FrameHeader, payload: &[u8]) -> Result<Option<ExtensionEvent>, String> {
+        self(header, payload)
+    }
+}
+
+/// A value produced by an [`ExtensionHandler`], of the handler's own type.
+///
+/// Compares equal to another event holding an equal value of the same type.
+pub struct ExtensionEvent(Box<dyn EventValue>);
+
+impl ExtensionEvent {
+    pub fn new<T: Any + std::fmt::Debug + PartialEq + Send>(value: T) -> Self {
+        Self(Box::new(value))
+    }
+
+    /// The value, if it is a `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.as_any().downcast_ref()
+    }
+
+    /// Take the value out, or get the event back if it is not a `T`.
+    pub fn downcast<T: Any>(self) -> Result<T, Self> {
+        if self.is::<T>() {
+            Ok(*self.0.into_any().downcast().expect("type checked above"))
+        } else {
+            Err(self)
+        }
+    }
+
+    pub fn is<T: Any>(&self) -> bool {
+        self.0.as_any().is::<T>()
+    }
+}
+
+impl std::fmt::Debug for ExtensionEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for ExtensionEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_value(other.0.as_any())
+    }
+}
+
+/// Object-safe view of an event value.
+trait EventValue: std::fmt::Debug + Send {
+    fn as_any(&self) -> &dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+    fn eq_value(&self, other: &dyn Any) -> bool;
+}
+
+impl<T: Any + std::fmt::Debug + PartialEq + Send> EventValue for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn eq_value(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<T>() == Some(self)
+    }
+}
+
+/// Registered handlers by frame type.
+#[derive(Default)]
+pub(crate) struct Extensions {
+    handlers: BTreeMap<u8, Box<dyn ExtensionHandler>>,
+}
+
+impl Extensions {
+    pub(crate) fn insert(&mut self, frame_type: u8, handler: Box<dyn ExtensionHandler>) {
+        self.handlers.insert(frame_type, handler);
+    }
+
+    pub(crate) fn remove(&mut self, frame_type: u8) -> bool {
+        self.handlers.remove(&frame_type).is_some()
+    }
+
+    pub(crate) fn get(&self, frame_type: u8) -> Option<&dyn ExtensionHandler> {
+        self.handlers.get(&frame_type).map(|h| h.as_ref())
+    }
+
+    pub(crate) fn get_mut(&mut self, frame_type: u8) -> Option<&mut (dyn ExtensionHandler + 'static)> {
+        self.handlers.get_mut(&frame_type).map(|h| h.as_mut())
+    }
+}
+
+impl std::fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.handlers.keys()).finish()
+    }
+}
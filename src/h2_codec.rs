@@ -10,7 +10,11 @@
 //!
 //! Reference: RFC 7540 (HTTP/2)
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::extension::{ExtensionEvent, ExtensionHandler, Extensions};
+use crate::snapshot::{SnapshotError, SnapshotReader, SnapshotWriter};
+use crate::stream::Role;
 
 /// HTTP/2 frame types (RFC 7540 Section 6)
 #[allow(dead_code)]
@@ -25,6 +29,12 @@ pub mod frame_type {
     pub const GOAWAY: u8 = 0x7;
     pub const WINDOW_UPDATE: u8 = 0x8;
     pub const CONTINUATION: u8 = 0x9;
+    /// RFC 7838 Section 4
+    pub const ALTSVC: u8 = 0xa;
+    /// RFC 8336 Section 2
+    pub const ORIGIN: u8 = 0xc;
+    /// RFC 9218 Section 7.1
+    pub const PRIORITY_UPDATE: u8 = 0x10;
 }
 
 /// HTTP/2 frame flags
@@ -46,6 +56,8 @@ pub mod settings_id {
     pub const MAX_FRAME_SIZE: u16 = 0x5;
     pub const MAX_HEADER_LIST_SIZE: u16 = 0x6;
     pub const ENABLE_CONNECT_PROTOCOL: u16 = 0x8;
+    /// RFC 9218 Section 2.1: 1 if the sender does not use RFC 7540 priorities
+    pub const NO_RFC7540_PRIORITIES: u16 = 0x9;
 }
 
 /// HTTP/2 error codes (RFC 7540 Section 7)
@@ -119,6 +131,45 @@ impl H2FrameHeader {
     }
 }
 
+/// Stream dependency and weight from a PRIORITY frame or a HEADERS frame
+/// with the PRIORITY flag (RFC 7540 Section 6.3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamPriority {
+    /// Exclusive dependency flag
+    pub exclusive: bool,
+    /// Stream this stream depends on (0 for the root)
+    pub dependency: u32,
+    /// Weight as sent on the wire (0-255); see `effective_weight`
+    pub weight: u8,
+}
+
+impl StreamPriority {
+    /// The weight used for scheduling, 1-256 (wire value plus one).
+    pub fn effective_weight(&self) -> u16 {
+        u16::from(self.weight) + 1
+    }
+
+    /// Parse the 5-byte priority fields: E bit + 31-bit dependency, then weight.
+    fn parse(bytes: &[u8]) -> Self {
+        let raw = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        Self {
+            exclusive: raw & 0x80000000 != 0,
+            dependency: raw & 0x7FFFFFFF,
+            weight: bytes[4],
+        }
+    }
+
+    /// Encode as the 5-byte priority fields.
+    fn to_bytes(self) -> [u8; 5] {
+        let mut raw = self.dependency & 0x7FFFFFFF;
+        if self.exclusive {
+            raw |= 0x80000000;
+        }
+        let [a, b, c, d] = raw.to_be_bytes();
+        [a, b, c, d, self.weight]
+    }
+}
+
 /// Events emitted by the H2 codec when parsing frames
 #[derive(Debug, PartialEq)]
 pub enum H2Event {
@@ -127,6 +178,8 @@ pub enum H2Event {
         stream_id: u32,
         header_block: Vec<u8>,  // HPACK-encoded headers
         end_stream: bool,
+        /// Priority fields, if the HEADERS frame had the PRIORITY flag
+        priority: Option<StreamPriority>,
     },
     /// Data for a stream
     Data {
@@ -160,6 +213,87 @@ pub enum H2Event {
         ack: bool,
         data: [u8; 8],
     },
+    /// Server push (PUSH_PROMISE, assembled with any CONTINUATION frames).
+    /// `header_block` encodes the promised request; decode it with the same
+    /// `HpackDecoder` as other header blocks. The promised stream is now
+    /// reserved (remote) until its response HEADERS arrive. Push promise
+    /// header blocks are assembled even with incremental header delivery on.
+    PushPromise {
+        /// Stream the promise was sent on (the associated request)
+        stream_id: u32,
+        promised_stream_id: u32,
+        header_block: Vec<u8>,  // HPACK-encoded request headers
+    },
+    /// Alternative services advertised by the server (RFC 7838 ALTSVC frame).
+    /// On stream 0 `origin` names the origin they apply to; on other streams
+    /// it is empty and they apply to that stream's origin. Parse
+    /// `field_value` with `AltSvc::parse`.
+    AltSvc {
+        stream_id: u32,
+        /// ASCII serialized origin, e.g. `https://example.com`
+        origin: String,
+        /// Alt-Svc field value, same syntax as the `alt-svc` header
+        field_value: Vec<u8>,
+    },
+    /// Origin Set announced by the server (RFC 8336 ORIGIN frame), for
+    /// deciding which origins may be coalesced onto this connection. Entries
+    /// that are not ASCII are left out; truncated frames are ignored.
+    Origin {
+        /// ASCII serialized origins, e.g. `https://example.com`, in frame order
+        origins: Vec<String>,
+    },
+    /// One fragment of a header block, emitted instead of `Headers` when
+    /// incremental header delivery is enabled (see
+    /// [`H2Codec::set_incremental_headers`]). Feed the fragments, in order,
+    /// to [`crate::HpackDecoder::decode_fragment`].
+    HeaderBlockFragment {
+        stream_id: u32,
+        fragment: Vec<u8>,  // HPACK-encoded fragment
+        /// True for the fragment that completes the header block.
+        end_headers: bool,
+        /// END_STREAM flag of the HEADERS frame that started the block.
+        end_stream: bool,
+        /// Priority fields of the HEADERS frame; always `None` for
+        /// CONTINUATION fragments.
+        priority: Option<StreamPriority>,
+    },
+    /// Stream priority change (PRIORITY frame)
+    Priority {
+        stream_id: u32,
+        priority: StreamPriority,
+    },
+    /// Extensible priority signal for a stream (RFC 9218 PRIORITY_UPDATE frame).
+    /// Parse `field_value` with [`crate::ExtensiblePriority::parse`].
+    PriorityUpdate {
+        prioritized_stream_id: u32,
+        /// Priority Field Value, same syntax as the `priority` header
+        field_value: Vec<u8>,
+    },
+    /// A frame broke a rule that affects only its stream. The connection
+    /// stays usable; reset the stream with `create_rst_stream(stream_id,
+    /// error_code)`. Emitted after the event for the offending frame, if any,
+    /// so a header block can still be fed to the HPACK decoder.
+    StreamError {
+        stream_id: u32,
+        error_code: u32,
+    },
+    /// A frame parsed by a handler registered with
+    /// `H2Codec::register_extension`.
+    Extension {
+        frame_type: u8,
+        stream_id: u32,
+        /// The handler's value; use `downcast_ref` to get its type back
+        event: ExtensionEvent,
+    },
+    /// A frame of a type the codec does not parse and no handler is
+    /// registered for. Only emitted after `H2Codec::set_emit_unknown_frames`;
+    /// otherwise such frames are ignored (RFC 9113 Section 5.5).
+    Unknown {
+        frame_type: u8,
+        flags: u8,
+        stream_id: u32,
+        payload: Vec<u8>,
+    },
 }
 
 /// State for a single HTTP/2 stream (lifecycle tracking only).
@@ -171,6 +305,9 @@ pub struct StreamState {
     pub headers_complete: bool,
     /// True if we've seen END_STREAM
     pub stream_ended: bool,
+    /// True if the peer promised this stream with PUSH_PROMISE and its
+    /// HEADERS have not arrived yet: the reserved (remote) state.
+    pub reserved_remote: bool,
 }
 
 /// HTTP/2 frame parser for the WASM kernel.
@@ -192,8 +329,72 @@ pub struct H2Codec {
     pending_headers_end_stream: bool,
     /// Accumulated header block data across HEADERS + CONTINUATION frames
     pending_header_block: Vec<u8>,
+    /// Emit header block fragments as they arrive instead of accumulating them
+    incremental_headers: bool,
+    /// Bytes of the pending header block already emitted as fragments
+    pending_header_len: usize,
+    /// Priority fields from the HEADERS frame that started the pending header block
+    pending_headers_priority: Option<StreamPriority>,
+    /// Promised stream ID if the pending header block belongs to a PUSH_PROMISE
+    pending_promised_stream: Option<u32>,
+    /// Highest stream ID the peer promised with PUSH_PROMISE (0 before the first)
+    last_promised_stream: u32,
+    /// Which end of the connection this codec parses frames for, if known
+    role: Option<Role>,
+    /// We advertised SETTINGS_ENABLE_PUSH=0 and the peer acknowledged it
+    push_disabled: bool,
+    /// The peer sent SETTINGS_ENABLE_PUSH=0
+    peer_push_disabled: bool,
+    /// The peer's SETTINGS_MAX_CONCURRENT_STREAMS, if it sent one
+    peer_max_concurrent_streams: Option<u32>,
+    /// Streams we promised with `push_promise` and have not removed
+    pushed_streams: BTreeSet<u32>,
+    /// Promised streams opened with `open_pushed_stream` (subset of `pushed_streams`)
+    open_pushed_streams: BTreeSet<u32>,
+    /// Last promised stream ID allocated by `push_promise` (0 before the first)
+    last_pushed_stream: u32,
+    /// Handlers for extension frame types
+    extensions: Extensions,
+    /// Report frames of unhandled types as `H2Event::Unknown`
+    emit_unknown_frames: bool,
 }
 
+/// Why [`H2Codec::push_promise`] could not promise a stream, or
+/// [`H2Codec::open_pushed_stream`] could not open one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushError {
+    /// Only a codec with the [`Role::Server`] role can push.
+    NotServer,
+    /// The peer sent SETTINGS_ENABLE_PUSH=0.
+    PushDisabled,
+    /// The associated stream (the held ID) is not an open client-initiated
+    /// stream (RFC 9113 Section 8.4.1).
+    StreamNotOpen(u32),
+    /// The held stream ID was not promised with `push_promise`, or has been
+    /// removed.
+    NotPromised(u32),
+    /// As many pushed streams are open as the peer's
+    /// SETTINGS_MAX_CONCURRENT_STREAMS allows (the held value).
+    ConcurrencyLimit(u32),
+    /// All even stream IDs have been used on this connection.
+    StreamIdsExhausted,
+}
+
+impl std::fmt::Display for PushError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PushError::NotServer => write!(f, "only servers can push"),
+            PushError::PushDisabled => write!(f, "peer disabled server push"),
+            PushError::StreamNotOpen(id) => write!(f, "stream {} is not an open client stream", id),
+            PushError::NotPromised(id) => write!(f, "stream {} is not a promised stream", id),
+            PushError::ConcurrencyLimit(max) => write!(f, "peer allows at most {} concurrent streams", max),
+            PushError::StreamIdsExhausted => write!(f, "no promised stream IDs left"),
+        }
+    }
+}
+
+impl std::error::Error for PushError {}
+
 /// Maximum accumulated header block size (256 KB).
 /// Prevents unbounded memory growth from malicious/buggy CONTINUATION floods.
 pub const MAX_HEADER_BLOCK_SIZE: usize = 256 * 1024;
@@ -202,6 +403,10 @@ pub const MAX_HEADER_BLOCK_SIZE: usize = 256 * 1024;
 /// Prevents unbounded memory growth from slow/partial frame delivery.
 pub const MAX_BUFFER_SIZE: usize = 1024 * 1024;
 
+/// Magic and format version of `H2Codec::snapshot` output.
+const SNAPSHOT_MAGIC: &[u8; 4] = b"H2CS";
+const SNAPSHOT_VERSION: u8 = 1;
+
 /// The HTTP/2 connection preface (24 bytes)
 pub const CONNECTION_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
 
@@ -210,6 +415,20 @@ pub fn is_h2c_preface(data: &[u8]) -> bool {
     data.len() >= CONNECTION_PREFACE.len() && &data[..CONNECTION_PREFACE.len()] == CONNECTION_PREFACE
 }
 
+/// RFC 7540 Section 5.3.1: a HEADERS frame whose priority fields make the
+/// stream depend on itself is a stream error of type PROTOCOL_ERROR.
+fn self_dependency_error(event: &H2Event) -> Option<H2Event> {
+    let (stream_id, priority) = match event {
+        H2Event::Headers { stream_id, priority: Some(priority), .. }
+        | H2Event::HeaderBlockFragment { stream_id, priority: Some(priority), .. } => (*stream_id, priority),
+        _ => return None,
+    };
+    (priority.dependency == stream_id).then_some(H2Event::StreamError {
+        stream_id,
+        error_code: error_code::PROTOCOL_ERROR,
+    })
+}
+
 
 impl H2Codec {
     pub fn new() -> Self {
@@ -271,7 +490,11 @@ impl H2Codec {
             // Parse the frame. On error, drain consumed bytes first to avoid
             // re-processing already-parsed frames on the next process() call.
             match self.parse_frame(&header, payload) {
-                Ok(Some(event)) => events.push(event),
+                Ok(Some(event)) => {
+                    let error = self_dependency_error(&event);
+                    events.push(event);
+                    events.extend(error);
+                }
                 Ok(None) => {}
                 Err(e) => {
                     if offset > 0 {
@@ -295,8 +518,13 @@ impl H2Codec {
         // RFC 7540 Section 6.10: While a header block is pending (between HEADERS
         // without END_HEADERS and the final CONTINUATION with END_HEADERS), no other
         // frame type may be received on ANY stream except CONTINUATION on the same stream.
+        // Extension handlers may opt out for their own frame types.
         if let Some(pending_stream) = self.pending_headers_stream {
-            if header.frame_type != frame_type::CONTINUATION {
+            let allowed = self
+                .extensions
+                .get(header.frame_type)
+                .is_some_and(|h| h.allowed_in_header_block(header, pending_stream));
+            if header.frame_type != frame_type::CONTINUATION && !allowed {
                 return Err(format!(
                     "Received frame type {} while CONTINUATION expected for stream {}",
                     header.frame_type, pending_stream
@@ -304,6 +532,15 @@ impl H2Codec {
             }
         }
 
+        if let Some(handler) = self.extensions.get_mut(header.frame_type) {
+            let event = handler.parse(header, &payload)?;
+            return Ok(event.map(|event| H2Event::Extension {
+                frame_type: header.frame_type,
+                stream_id: header.stream_id,
+                event,
+            }));
+        }
+
         match header.frame_type {
             frame_type::DATA => {
                 // RFC 7540 Section 6.1: DATA frames MUST be associated with a stream.
@@ -326,29 +563,49 @@ impl H2Codec {
                 if header.stream_id == 0 {
                     return Err("HEADERS frame on stream 0".to_string());
                 }
-                let header_block = self.extract_headers_payload(header, payload)?;
+                let (priority, header_block) = self.extract_headers_payload(header, payload)?;
                 let stream = self.streams.entry(header.stream_id).or_default();
+                stream.reserved_remote = false;
                 if header.is_end_stream() {
                     stream.stream_ended = true;
                 }
-                if header.is_end_headers() {
+                // The first fragment of a block continued by CONTINUATION
+                // counts against the same bound in both modes.
+                if !header.is_end_headers() && header_block.len() > MAX_HEADER_BLOCK_SIZE {
+                    return Err(format!(
+                        "Header block too large ({} bytes, max {})",
+                        header_block.len(), MAX_HEADER_BLOCK_SIZE
+                    ));
+                }
+                if self.incremental_headers {
+                    if header.is_end_headers() {
+                        stream.headers_complete = true;
+                    } else {
+                        self.pending_headers_stream = Some(header.stream_id);
+                        self.pending_headers_end_stream = header.is_end_stream();
+                        self.pending_header_len = header_block.len();
+                    }
+                    Ok(Some(H2Event::HeaderBlockFragment {
+                        stream_id: header.stream_id,
+                        fragment: header_block,
+                        end_headers: header.is_end_headers(),
+                        end_stream: header.is_end_stream(),
+                        priority,
+                    }))
+                } else if header.is_end_headers() {
                     // Complete header block in a single frame
                     stream.headers_complete = true;
                     Ok(Some(H2Event::Headers {
                         stream_id: header.stream_id,
                         header_block,
                         end_stream: header.is_end_stream(),
+                        priority,
                     }))
                 } else {
                     // Headers span multiple frames - accumulate and wait for CONTINUATION
-                    if header_block.len() > MAX_HEADER_BLOCK_SIZE {
-                        return Err(format!(
-                            "Header block too large ({} bytes, max {})",
-                            header_block.len(), MAX_HEADER_BLOCK_SIZE
-                        ));
-                    }
                     self.pending_headers_stream = Some(header.stream_id);
                     self.pending_headers_end_stream = header.is_end_stream();
+                    self.pending_headers_priority = priority;
                     self.pending_header_block = header_block;
                     Ok(None)
                 }
@@ -360,17 +617,47 @@ impl H2Codec {
                             header.stream_id, pending_stream));
                     }
                     // Guard against unbounded header block accumulation
-                    let new_size = self.pending_header_block.len() + payload.len();
+                    let new_size = self.pending_header_len + self.pending_header_block.len() + payload.len();
                     if new_size > MAX_HEADER_BLOCK_SIZE {
                         self.pending_headers_stream = None;
                         self.pending_header_block.clear();
+                        self.pending_header_len = 0;
+                        self.pending_headers_priority = None;
+                        self.pending_promised_stream = None;
                         return Err(format!(
                             "Header block too large ({} bytes, max {})",
                             new_size, MAX_HEADER_BLOCK_SIZE
                         ));
                     }
+                    if self.incremental_headers && self.pending_promised_stream.is_none() {
+                        let end_stream = self.pending_headers_end_stream;
+                        if header.is_end_headers() {
+                            let stream = self.streams.entry(header.stream_id).or_default();
+                            stream.headers_complete = true;
+                            self.pending_headers_stream = None;
+                            self.pending_headers_end_stream = false;
+                            self.pending_header_len = 0;
+                        } else {
+                            self.pending_header_len = new_size;
+                        }
+                        return Ok(Some(H2Event::HeaderBlockFragment {
+                            stream_id: header.stream_id,
+                            fragment: payload,
+                            end_headers: header.is_end_headers(),
+                            end_stream,
+                            priority: None,
+                        }));
+                    }
                     self.pending_header_block.extend_from_slice(&payload);
                     if header.is_end_headers() {
+                        if let Some(promised_stream_id) = self.pending_promised_stream.take() {
+                            self.pending_headers_stream = None;
+                            return Ok(Some(H2Event::PushPromise {
+                                stream_id: header.stream_id,
+                                promised_stream_id,
+                                header_block: std::mem::take(&mut self.pending_header_block),
+                            }));
+                        }
                         let stream = self.streams.entry(header.stream_id).or_default();
                         stream.headers_complete = true;
                         let full_block = std::mem::take(&mut self.pending_header_block);
@@ -381,6 +668,7 @@ impl H2Codec {
                             stream_id: header.stream_id,
                             header_block: full_block,
                             end_stream,
+                            priority: self.pending_headers_priority.take(),
                         }))
                     } else {
                         Ok(None)
@@ -440,6 +728,19 @@ impl H2Codec {
                         payload[pos + 2], payload[pos + 3],
                         payload[pos + 4], payload[pos + 5],
                     ]);
+                    // RFC 9218 Section 2.1: values other than 0 or 1 are a
+                    // connection error of type PROTOCOL_ERROR.
+                    if id == settings_id::NO_RFC7540_PRIORITIES && value > 1 {
+                        return Err(format!(
+                            "SETTINGS_NO_RFC7540_PRIORITIES with invalid value {}",
+                            value
+                        ));
+                    }
+                    match id {
+                        settings_id::ENABLE_PUSH => self.peer_push_disabled = value == 0,
+                        settings_id::MAX_CONCURRENT_STREAMS => self.peer_max_concurrent_streams = Some(value),
+                        _ => {}
+                    }
                     settings.push((id, value));
                     pos += 6;
                 }
@@ -511,16 +812,156 @@ impl H2Codec {
                         payload.len()
                     ));
                 }
-                // Ignore PRIORITY frames (no event emitted)
-                Ok(None)
+                let priority = StreamPriority::parse(&payload);
+                // RFC 7540 Section 5.3.1: a stream cannot depend on itself.
+                if priority.dependency == header.stream_id {
+                    return Ok(Some(H2Event::StreamError {
+                        stream_id: header.stream_id,
+                        error_code: error_code::PROTOCOL_ERROR,
+                    }));
+                }
+                Ok(Some(H2Event::Priority {
+                    stream_id: header.stream_id,
+                    priority,
+                }))
+            }
+            frame_type::PRIORITY_UPDATE => {
+                // RFC 9218 Section 7.1: PRIORITY_UPDATE is sent on stream 0 only.
+                if header.stream_id != 0 {
+                    return Err("PRIORITY_UPDATE frame on non-zero stream".to_string());
+                }
+                // Only clients send it; a client receiving one treats it as
+                // a connection error of type PROTOCOL_ERROR.
+                if self.role == Some(Role::Client) {
+                    return Err("PROTOCOL_ERROR: PRIORITY_UPDATE received by a client".to_string());
+                }
+                if payload.len() < 4 {
+                    return Err(format!(
+                        "PRIORITY_UPDATE frame size error: expected at least 4 bytes, got {}",
+                        payload.len()
+                    ));
+                }
+                let prioritized_stream_id = u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]) & 0x7FFFFFFF;
+                if prioritized_stream_id == 0 {
+                    return Err("PRIORITY_UPDATE for stream 0".to_string());
+                }
+                Ok(Some(H2Event::PriorityUpdate {
+                    prioritized_stream_id,
+                    field_value: payload[4..].to_vec(),
+                }))
+            }
+            frame_type::ALTSVC => {
+                // RFC 7838 Section 4: malformed ALTSVC frames are ignored, as
+                // are frames with the origin on the wrong kind of stream
+                // (stream 0 needs one, other streams must not carry one).
+                if payload.len() < 2 {
+                    return Ok(None);
+                }
+                let origin_len = u16::from_be_bytes([payload[0], payload[1]]) as usize;
+                if payload.len() - 2 < origin_len || (header.stream_id == 0) == (origin_len == 0) {
+                    return Ok(None);
+                }
+                let origin = &payload[2..2 + origin_len];
+                if !origin.is_ascii() {
+                    return Ok(None);
+                }
+                Ok(Some(H2Event::AltSvc {
+                    stream_id: header.stream_id,
+                    origin: String::from_utf8(origin.to_vec()).expect("ASCII is UTF-8"),
+                    field_value: payload[2 + origin_len..].to_vec(),
+                }))
+            }
+            frame_type::ORIGIN => {
+                // RFC 8336 Section 2: ORIGIN on any stream other than 0 is
+                // invalid and MUST be ignored. As an optional extension,
+                // frames and entries the codec cannot use are dropped too.
+                if header.stream_id != 0 {
+                    return Ok(None);
+                }
+                let mut origins = Vec::new();
+                let mut pos = 0;
+                while pos < payload.len() {
+                    // Truncated entry: ignore the whole frame
+                    if payload.len() - pos < 2 {
+                        return Ok(None);
+                    }
+                    let len = u16::from_be_bytes([payload[pos], payload[pos + 1]]) as usize;
+                    pos += 2;
+                    if payload.len() - pos < len {
+                        return Ok(None);
+                    }
+                    let entry = &payload[pos..pos + len];
+                    if entry.is_ascii() {
+                        origins.push(String::from_utf8(entry.to_vec()).expect("ASCII is UTF-8"));
+                    }
+                    pos += len;
+                }
+                Ok(Some(H2Event::Origin { origins }))
             }
             frame_type::PUSH_PROMISE => {
-                // We don't support server push in the proxy
+                // RFC 7540 Section 6.6: PUSH_PROMISE MUST be associated with a stream.
+                if header.stream_id == 0 {
+                    return Err("PUSH_PROMISE frame on stream 0".to_string());
+                }
+                // RFC 9113 Section 8.4: clients cannot push, and a client that
+                // disabled push treats a promise as PROTOCOL_ERROR.
+                if self.role == Some(Role::Server) {
+                    return Err("PROTOCOL_ERROR: PUSH_PROMISE received by a server".to_string());
+                }
+                if self.push_disabled {
+                    return Err("PROTOCOL_ERROR: PUSH_PROMISE received with push disabled".to_string());
+                }
+                let (promised_stream_id, header_block) = self.extract_push_promise_payload(header, payload)?;
+                // RFC 7540 Section 5.1.1: pushed streams are server-initiated
+                // (even) and the promised stream must be idle.
+                if promised_stream_id == 0 || promised_stream_id % 2 != 0 {
+                    return Err(format!(
+                        "PUSH_PROMISE with invalid promised stream ID {}",
+                        promised_stream_id
+                    ));
+                }
+                // Stream IDs are never reused, so a promised ID not above the
+                // last one belongs to a stream that has already been used.
+                if promised_stream_id <= self.last_promised_stream || self.streams.contains_key(&promised_stream_id) {
+                    return Err(format!(
+                        "PROTOCOL_ERROR: PUSH_PROMISE for stream {} that is not idle",
+                        promised_stream_id
+                    ));
+                }
+                self.last_promised_stream = promised_stream_id;
+                self.streams.insert(promised_stream_id, StreamState {
+                    reserved_remote: true,
+                    ..StreamState::default()
+                });
+                if header.is_end_headers() {
+                    return Ok(Some(H2Event::PushPromise {
+                        stream_id: header.stream_id,
+                        promised_stream_id,
+                        header_block,
+                    }));
+                }
+                if header_block.len() > MAX_HEADER_BLOCK_SIZE {
+                    return Err(format!(
+                        "Header block too large ({} bytes, max {})",
+                        header_block.len(), MAX_HEADER_BLOCK_SIZE
+                    ));
+                }
+                self.pending_headers_stream = Some(header.stream_id);
+                self.pending_promised_stream = Some(promised_stream_id);
+                self.pending_header_block = header_block;
                 Ok(None)
             }
             _ => {
                 // Unknown frame type - ignore per RFC 7540 Section 4.1
-                Ok(None)
+                if !self.emit_unknown_frames {
+                    return Ok(None);
+                }
+                Ok(Some(H2Event::Unknown {
+                    frame_type: header.frame_type,
+                    flags: header.flags,
+                    stream_id: header.stream_id,
+                    payload,
+                }))
             }
         }
     }
@@ -544,8 +985,9 @@ impl H2Codec {
         }
     }
 
-    /// Extract HEADERS payload, handling PADDED and PRIORITY flags.
-    fn extract_headers_payload(&self, header: &H2FrameHeader, payload: Vec<u8>) -> Result<Vec<u8>, String> {
+    /// Extract HEADERS payload, handling PADDED and PRIORITY flags. Returns the
+    /// priority fields, if present, and the header block fragment.
+    fn extract_headers_payload(&self, header: &H2FrameHeader, payload: Vec<u8>) -> Result<(Option<StreamPriority>, Vec<u8>), String> {
         let mut offset = 0;
         let mut end = payload.len();
 
@@ -563,24 +1005,60 @@ impl H2Codec {
         }
 
         // Handle PRIORITY flag
+        let mut priority = None;
         if header.flags & flags::PRIORITY != 0 {
             if end - offset < 5 {
                 return Err("PRIORITY HEADERS frame with insufficient data".to_string());
             }
-            offset += 5; // Skip stream dependency (4 bytes) + weight (1 byte)
+            priority = Some(StreamPriority::parse(&payload[offset..offset + 5]));
+            offset += 5; // Stream dependency (4 bytes) + weight (1 byte)
         }
 
         // Avoid unnecessary copy when no stripping is needed
         if offset == 0 && end == payload.len() {
-            return Ok(payload);
+            return Ok((priority, payload));
         }
 
-        Ok(payload[offset..end].to_vec())
+        Ok((priority, payload[offset..end].to_vec()))
+    }
+
+    /// Extract PUSH_PROMISE payload, handling the PADDED flag. Returns the
+    /// promised stream ID and the header block fragment.
+    fn extract_push_promise_payload(&self, header: &H2FrameHeader, payload: Vec<u8>) -> Result<(u32, Vec<u8>), String> {
+        let mut offset = 0;
+        let mut end = payload.len();
+
+        if header.flags & flags::PADDED != 0 {
+            if payload.is_empty() {
+                return Err("PADDED PUSH_PROMISE frame with no payload".to_string());
+            }
+            let pad_length = payload[0] as usize;
+            offset = 1;
+            if pad_length > payload.len() - offset {
+                return Err("Invalid padding length in PUSH_PROMISE frame".to_string());
+            }
+            end = payload.len() - pad_length;
+        }
+
+        if end - offset < 4 {
+            return Err("PUSH_PROMISE frame too short".to_string());
+        }
+        let promised_stream_id = u32::from_be_bytes([
+            payload[offset], payload[offset + 1], payload[offset + 2], payload[offset + 3],
+        ]) & 0x7FFFFFFF;
+        Ok((promised_stream_id, payload[offset + 4..end].to_vec()))
+    }
+
+    /// State of a tracked stream, if any.
+    pub fn stream_state(&self, stream_id: u32) -> Option<&StreamState> {
+        self.streams.get(&stream_id)
     }
 
     /// Remove a stream (e.g., after completing a flow)
     pub fn remove_stream(&mut self, stream_id: u32) {
         self.streams.remove(&stream_id);
+        self.pushed_streams.remove(&stream_id);
+        self.open_pushed_streams.remove(&stream_id);
     }
 
     /// Return the number of tracked streams.
@@ -593,7 +1071,186 @@ impl H2Codec {
         self.streams.len()
     }
 
-    /// Reset codec state (e.g., after upstream reconnect)
+    /// Deliver header blocks as `H2Event::HeaderBlockFragment` events, one per
+    /// HEADERS or CONTINUATION frame, instead of buffering the block until
+    /// END_HEADERS and emitting `H2Event::Headers`.
+    ///
+    /// Paired with [`crate::HpackDecoder::decode_fragment`], this keeps only
+    /// the current frame in memory and lets header list limits be enforced
+    /// before the block is complete. CONTINUATION sequencing and
+    /// `MAX_HEADER_BLOCK_SIZE` are still enforced. Switch modes only between
+    /// header blocks.
+    pub fn set_incremental_headers(&mut self, enabled: bool) {
+        self.incremental_headers = enabled;
+    }
+
+    /// Whether incremental header delivery is enabled.
+    pub fn incremental_headers(&self) -> bool {
+        self.incremental_headers
+    }
+
+    /// Set which end of the connection this codec parses frames for. A
+    /// server rejects every PUSH_PROMISE as a connection error. Without a
+    /// role, the codec accepts frames from either side.
+    pub fn set_role(&mut self, role: Role) {
+        self.role = Some(role);
+    }
+
+    /// The role set with [`H2Codec::set_role`], if any.
+    pub fn role(&self) -> Option<Role> {
+        self.role
+    }
+
+    /// Record the SETTINGS_ENABLE_PUSH value we advertised, once the peer has
+    /// acknowledged it (default: enabled). With push disabled, PUSH_PROMISE
+    /// is a connection error of type PROTOCOL_ERROR (RFC 9113 Section 6.6).
+    pub fn set_enable_push(&mut self, enabled: bool) {
+        self.push_disabled = !enabled;
+    }
+
+    /// Whether PUSH_PROMISE frames are accepted, considering both the local
+    /// ENABLE_PUSH setting and the role.
+    pub fn enable_push(&self) -> bool {
+        !self.push_disabled && self.role != Some(Role::Server)
+    }
+
+    /// Refuse a pushed stream announced by `H2Event::PushPromise`: forget its
+    /// reserved state and return the RST_STREAM frame to send, typically with
+    /// `error_code::CANCEL` (not wanted) or `error_code::REFUSED_STREAM`
+    /// (not processed).
+    pub fn refuse_push(&mut self, promised_stream_id: u32, error_code: u32) -> Vec<u8> {
+        self.streams.remove(&promised_stream_id);
+        Self::create_rst_stream(promised_stream_id, error_code)
+    }
+
+    /// Promise a server push on client stream `stream_id`: allocate the next
+    /// even stream ID and build the PUSH_PROMISE frame(s) carrying
+    /// `header_block`, the HPACK-encoded request being pushed.
+    ///
+    /// Fails if the codec's role is not [`Role::Server`], if the peer
+    /// disabled push, or if `stream_id` is not a client-initiated stream the
+    /// codec has seen HEADERS for and not seen reset or removed: only open
+    /// and half-closed (remote) streams can carry a promise (RFC 9113
+    /// Section 8.4.1). The codec only parses what the peer sends, so do not
+    /// push on a stream after sending its END_STREAM yourself.
+    ///
+    /// The promised stream is only reserved, and reserved streams do not
+    /// count against the peer's MAX_CONCURRENT_STREAMS (RFC 9113 Section
+    /// 5.1.2). The limit is enforced by [`H2Codec::open_pushed_stream`] when
+    /// the pushed response starts; `push_promise` only refuses with
+    /// `ConcurrencyLimit` while the open pushed streams already reach it, so
+    /// a promise is made only if it could be opened right away.
+    ///
+    /// # Panics
+    /// Panics if `max_frame_size` is invalid (see
+    /// [`H2Codec::create_push_promise_frames`]).
+    pub fn push_promise(&mut self, stream_id: u32, header_block: &[u8], max_frame_size: u32) -> Result<(u32, Vec<Vec<u8>>), PushError> {
+        if self.role != Some(Role::Server) {
+            return Err(PushError::NotServer);
+        }
+        if self.peer_push_disabled {
+            return Err(PushError::PushDisabled);
+        }
+        let open = stream_id % 2 == 1 && self.streams.get(&stream_id).is_some_and(|s| !s.reserved_remote);
+        if !open {
+            return Err(PushError::StreamNotOpen(stream_id));
+        }
+        self.check_push_concurrency()?;
+        let promised_stream_id = self.last_pushed_stream + 2;
+        if promised_stream_id > 0x7FFFFFFF {
+            return Err(PushError::StreamIdsExhausted);
+        }
+        let frames = Self::create_push_promise_frames(stream_id, promised_stream_id, header_block, max_frame_size);
+        self.last_pushed_stream = promised_stream_id;
+        self.pushed_streams.insert(promised_stream_id);
+        Ok((promised_stream_id, frames))
+    }
+
+    /// Number of streams promised with [`H2Codec::push_promise`] that have
+    /// not been removed.
+    pub fn pushed_stream_count(&self) -> usize {
+        self.pushed_streams.len()
+    }
+
+    /// Open a stream promised with [`H2Codec::push_promise`], before sending
+    /// the HEADERS of its response. Fails if as many pushed streams are
+    /// already open as the peer's MAX_CONCURRENT_STREAMS allows; the stream
+    /// then stays reserved and can be opened later. An open pushed stream
+    /// counts until [`H2Codec::remove_stream`] is called for it. Opening a
+    /// stream that is already open succeeds; a stream that was not promised
+    /// with `push_promise`, or has been removed, fails with `NotPromised`.
+    pub fn open_pushed_stream(&mut self, promised_stream_id: u32) -> Result<(), PushError> {
+        if !self.pushed_streams.contains(&promised_stream_id) {
+            return Err(PushError::NotPromised(promised_stream_id));
+        }
+        if self.open_pushed_streams.contains(&promised_stream_id) {
+            return Ok(());
+        }
+        self.check_push_concurrency()?;
+        self.open_pushed_streams.insert(promised_stream_id);
+        Ok(())
+    }
+
+    /// `ConcurrencyLimit` if the open pushed streams reach the peer's
+    /// MAX_CONCURRENT_STREAMS.
+    fn check_push_concurrency(&self) -> Result<(), PushError> {
+        match self.peer_max_concurrent_streams {
+            Some(max) if self.open_pushed_streams.len() as u64 >= u64::from(max) => Err(PushError::ConcurrencyLimit(max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Number of pushed streams opened with [`H2Codec::open_pushed_stream`]
+    /// that have not been removed.
+    pub fn open_pushed_stream_count(&self) -> usize {
+        self.open_pushed_streams.len()
+    }
+
+    /// Parse frames of `frame_type` with `handler`, reporting its results as
+    /// `H2Event::Extension`. Replaces any handler already registered for the
+    /// type, and takes precedence over the codec's own parsing of extension
+    /// frames (ALTSVC, ORIGIN, PRIORITY_UPDATE).
+    ///
+    /// Handlers are not part of [`H2Codec::snapshot`]; register them again
+    /// after [`H2Codec::restore`].
+    ///
+    /// # Panics
+    /// Panics if `frame_type` is one of the RFC 9113 frame types
+    /// (DATA through CONTINUATION).
+    pub fn register_extension(&mut self, frame_type: u8, handler: impl ExtensionHandler + 'static) {
+        assert!(
+            frame_type > frame_type::CONTINUATION,
+            "frame type {:#x} is a core HTTP/2 frame type and cannot be handled by an extension",
+            frame_type
+        );
+        self.extensions.insert(frame_type, Box::new(handler));
+    }
+
+    /// Remove the handler for `frame_type`. Returns false if none was registered.
+    pub fn unregister_extension(&mut self, frame_type: u8) -> bool {
+        self.extensions.remove(frame_type)
+    }
+
+    /// Whether a handler is registered for `frame_type`.
+    pub fn has_extension(&self, frame_type: u8) -> bool {
+        self.extensions.get(frame_type).is_some()
+    }
+
+    /// Report frames of types the codec does not parse, and no handler is
+    /// registered for, as `H2Event::Unknown` instead of ignoring them
+    /// (default: ignored).
+    pub fn set_emit_unknown_frames(&mut self, enabled: bool) {
+        self.emit_unknown_frames = enabled;
+    }
+
+    /// Whether unknown frames are reported as `H2Event::Unknown`.
+    pub fn emit_unknown_frames(&self) -> bool {
+        self.emit_unknown_frames
+    }
+
+    /// Reset codec state (e.g., after upstream reconnect). Configuration such
+    /// as incremental header delivery, role, push setting, extension handlers
+    /// and unknown frame reporting is kept.
     pub fn reset(&mut self) {
         self.buffer.clear();
         self.streams.clear();
@@ -601,6 +1258,193 @@ impl H2Codec {
         self.pending_headers_stream = None;
         self.pending_headers_end_stream = false;
         self.pending_header_block.clear();
+        self.pending_header_len = 0;
+        self.pending_headers_priority = None;
+        self.pending_promised_stream = None;
+        self.last_promised_stream = 0;
+        self.peer_push_disabled = false;
+        self.peer_max_concurrent_streams = None;
+        self.pushed_streams.clear();
+        self.open_pushed_streams.clear();
+        self.last_pushed_stream = 0;
+    }
+
+    /// Export the complete parsing state: buffered partial frames, per-stream
+    /// state, preface status and any header block still waiting for
+    /// CONTINUATION frames.
+    ///
+    /// The result starts with a format version tag and can be passed to
+    /// [`H2Codec::restore`] in another process or WASM instance to resume the
+    /// connection exactly where it stopped, even in the middle of a frame or
+    /// header block. HPACK state is separate: snapshot the connection's
+    /// `HpackDecoder` and `HpackEncoder` alongside.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut w = SnapshotWriter::new(SNAPSHOT_MAGIC, SNAPSHOT_VERSION);
+        w.bool(self.preface_received);
+        w.bytes(&self.buffer);
+
+        let mut stream_ids: Vec<u32> = self.streams.keys().copied().collect();
+        stream_ids.sort_unstable();
+        w.u32(stream_ids.len() as u32);
+        for id in stream_ids {
+            let stream = &self.streams[&id];
+            w.u32(id);
+            w.bool(stream.headers_complete);
+            w.bool(stream.stream_ended);
+        }
+
+        w.bool(self.pending_headers_stream.is_some());
+        w.u32(self.pending_headers_stream.unwrap_or(0));
+        w.bool(self.pending_headers_end_stream);
+        w.bytes(&self.pending_header_block);
+        w.bool(self.incremental_headers);
+        w.u64(self.pending_header_len as u64);
+        let priority = self.pending_headers_priority;
+        w.bool(priority.is_some());
+        w.bytes(&priority.map_or([0; 5], StreamPriority::to_bytes));
+        w.bool(self.pending_promised_stream.is_some());
+        w.u32(self.pending_promised_stream.unwrap_or(0));
+        w.u32(self.last_promised_stream);
+
+        let mut reserved: Vec<u32> = self.streams.iter().filter(|(_, s)| s.reserved_remote).map(|(&id, _)| id).collect();
+        reserved.sort_unstable();
+        w.u32(reserved.len() as u32);
+        for id in reserved {
+            w.u32(id);
+        }
+        w.u8(match self.role {
+            None => 0,
+            Some(Role::Client) => 1,
+            Some(Role::Server) => 2,
+        });
+        w.bool(self.push_disabled);
+        w.bool(self.peer_push_disabled);
+        w.bool(self.peer_max_concurrent_streams.is_some());
+        w.u32(self.peer_max_concurrent_streams.unwrap_or(0));
+        w.u32(self.last_pushed_stream);
+        w.u32(self.pushed_streams.len() as u32);
+        for &id in &self.pushed_streams {
+            w.u32(id);
+        }
+        w.u32(self.open_pushed_streams.len() as u32);
+        for &id in &self.open_pushed_streams {
+            w.u32(id);
+        }
+        w.bool(self.emit_unknown_frames);
+        w.finish()
+    }
+
+    /// Rebuild a codec from the output of [`H2Codec::snapshot`]. Extension
+    /// handlers are not restored.
+    pub fn restore(snapshot: &[u8]) -> Result<Self, SnapshotError> {
+        let mut r = SnapshotReader::new(snapshot, SNAPSHOT_MAGIC, SNAPSHOT_VERSION)?;
+        let preface_received = r.bool()?;
+        let buffer = r.bytes()?.to_vec();
+        if buffer.len() > MAX_BUFFER_SIZE {
+            return Err(SnapshotError::Invalid("buffer exceeds MAX_BUFFER_SIZE"));
+        }
+
+        let count = r.u32()?;
+        let mut streams = HashMap::new();
+        for _ in 0..count {
+            let id = r.u32()?;
+            let state = StreamState {
+                headers_complete: r.bool()?,
+                stream_ended: r.bool()?,
+                reserved_remote: false,
+            };
+            if streams.insert(id, state).is_some() {
+                return Err(SnapshotError::Invalid("duplicate stream id"));
+            }
+        }
+
+        let has_pending = r.bool()?;
+        let pending_id = r.u32()?;
+        let pending_headers_end_stream = r.bool()?;
+        let pending_header_block = r.bytes()?.to_vec();
+        let incremental_headers = r.bool()?;
+        let pending_header_len = r.usize()?;
+        let has_priority = r.bool()?;
+        let priority_bytes = r.bytes()?;
+        if priority_bytes.len() != 5 {
+            return Err(SnapshotError::Invalid("priority fields must be 5 bytes"));
+        }
+        let pending_headers_priority = has_priority.then(|| StreamPriority::parse(priority_bytes));
+        let has_promised = r.bool()?;
+        let promised_id = r.u32()?;
+        let last_promised_stream = r.u32()?;
+        if has_promised && promised_id > last_promised_stream {
+            return Err(SnapshotError::Invalid("pending promised stream above the last promised stream"));
+        }
+        let reserved_count = r.u32()?;
+        for _ in 0..reserved_count {
+            let id = r.u32()?;
+            if id > last_promised_stream {
+                return Err(SnapshotError::Invalid("reserved stream above the last promised stream"));
+            }
+            streams
+                .get_mut(&id)
+                .ok_or(SnapshotError::Invalid("reserved stream not tracked"))?
+                .reserved_remote = true;
+        }
+        let role = match r.u8()? {
+            0 => None,
+            1 => Some(Role::Client),
+            2 => Some(Role::Server),
+            _ => return Err(SnapshotError::Invalid("unknown role")),
+        };
+        let push_disabled = r.bool()?;
+        let peer_push_disabled = r.bool()?;
+        let has_max_concurrent = r.bool()?;
+        let max_concurrent = r.u32()?;
+        let last_pushed_stream = r.u32()?;
+        let pushed_count = r.u32()?;
+        let mut pushed_streams = BTreeSet::new();
+        for _ in 0..pushed_count {
+            let id = r.u32()?;
+            if id % 2 != 0 || id > last_pushed_stream || !pushed_streams.insert(id) {
+                return Err(SnapshotError::Invalid("invalid pushed stream id"));
+            }
+        }
+        let open_pushed_count = r.u32()?;
+        let mut open_pushed_streams = BTreeSet::new();
+        for _ in 0..open_pushed_count {
+            let id = r.u32()?;
+            if !pushed_streams.contains(&id) || !open_pushed_streams.insert(id) {
+                return Err(SnapshotError::Invalid("invalid open pushed stream id"));
+            }
+        }
+        let emit_unknown_frames = r.bool()?;
+        r.finish()?;
+        if pending_header_block.len().saturating_add(pending_header_len) > MAX_HEADER_BLOCK_SIZE {
+            return Err(SnapshotError::Invalid("pending header block exceeds MAX_HEADER_BLOCK_SIZE"));
+        }
+        if !has_pending && (pending_headers_end_stream || !pending_header_block.is_empty() || pending_header_len != 0 || has_priority || has_promised) {
+            return Err(SnapshotError::Invalid("pending header data without a pending stream"));
+        }
+
+        Ok(Self {
+            buffer,
+            streams,
+            preface_received,
+            pending_headers_stream: has_pending.then_some(pending_id),
+            pending_headers_end_stream,
+            pending_header_block,
+            incremental_headers,
+            pending_header_len,
+            pending_headers_priority,
+            pending_promised_stream: has_promised.then_some(promised_id),
+            last_promised_stream,
+            role,
+            push_disabled,
+            peer_push_disabled,
+            peer_max_concurrent_streams: has_max_concurrent.then_some(max_concurrent),
+            pushed_streams,
+            open_pushed_streams,
+            last_pushed_stream,
+            extensions: Extensions::default(),
+            emit_unknown_frames,
+        })
     }
 
     /// Set preface_received flag (for testing)
@@ -707,6 +1551,31 @@ impl H2Codec {
         frame
     }
 
+    /// Create a SETTINGS frame carrying the given (identifier, value) pairs, in order.
+    ///
+    /// # Panics
+    /// Panics if the payload exceeds the maximum 24-bit frame length (16,777,215).
+    pub fn create_settings_frame(settings: &[(u16, u32)]) -> Vec<u8> {
+        let length = settings.len() * 6;
+        assert!(
+            length <= MAX_FRAME_PAYLOAD_LENGTH as usize,
+            "Settings payload length {} exceeds maximum frame payload length {}",
+            length, MAX_FRAME_PAYLOAD_LENGTH
+        );
+        let mut frame = Vec::with_capacity(9 + length);
+        frame.push((length >> 16) as u8);
+        frame.push((length >> 8) as u8);
+        frame.push(length as u8);
+        frame.push(frame_type::SETTINGS);
+        frame.push(0x0); // Flags: 0 (not ACK)
+        frame.extend_from_slice(&[0, 0, 0, 0]); // Stream ID: 0
+        for &(id, value) in settings {
+            frame.extend_from_slice(&id.to_be_bytes());
+            frame.extend_from_slice(&value.to_be_bytes());
+        }
+        frame
+    }
+
     /// Create a PING ACK frame
     #[allow(dead_code)]
     pub fn create_ping_ack(data: [u8; 8]) -> Vec<u8> {
@@ -810,6 +1679,149 @@ impl H2Codec {
         frame
     }
 
+    /// Create a single HEADERS frame with the PRIORITY flag set, carrying the
+    /// given stream dependency and weight ahead of the header block.
+    ///
+    /// # Panics
+    /// Panics if the header block plus the 5 priority bytes exceeds the maximum
+    /// 24-bit frame length (16,777,215).
+    pub fn create_headers_frame_with_priority(stream_id: u32, header_block: &[u8], end_stream: bool, priority: StreamPriority) -> Vec<u8> {
+        assert!(
+            header_block.len() + 5 <= MAX_FRAME_PAYLOAD_LENGTH as usize,
+            "Header block length {} exceeds maximum frame payload length {}",
+            header_block.len() + 5, MAX_FRAME_PAYLOAD_LENGTH
+        );
+        let stream_id = stream_id & 0x7FFFFFFF; // Clear reserved bit
+        let length = header_block.len() + 5;
+        let mut flags_byte = flags::END_HEADERS | flags::PRIORITY;
+        if end_stream {
+            flags_byte |= flags::END_STREAM;
+        }
+
+        let mut frame = Vec::with_capacity(9 + length);
+        frame.push((length >> 16) as u8);
+        frame.push((length >> 8) as u8);
+        frame.push(length as u8);
+        frame.push(frame_type::HEADERS);
+        frame.push(flags_byte);
+        frame.extend_from_slice(&stream_id.to_be_bytes());
+        frame.extend_from_slice(&priority.to_bytes());
+        frame.extend_from_slice(header_block);
+        frame
+    }
+
+    /// Create a PRIORITY frame.
+    ///
+    /// # Panics
+    /// Panics if `stream_id` is 0 or the stream depends on itself.
+    pub fn create_priority_frame(stream_id: u32, priority: StreamPriority) -> Vec<u8> {
+        let stream_id = stream_id & 0x7FFFFFFF; // Clear reserved bit
+        assert!(stream_id != 0, "PRIORITY frame requires a non-zero stream ID");
+        assert!(
+            priority.dependency & 0x7FFFFFFF != stream_id,
+            "Stream {} cannot depend on itself",
+            stream_id
+        );
+        let mut frame = vec![0, 0, 5, frame_type::PRIORITY, 0];
+        frame.extend_from_slice(&stream_id.to_be_bytes());
+        frame.extend_from_slice(&priority.to_bytes());
+        frame
+    }
+
+    /// Create an ALTSVC frame (RFC 7838 Section 4). On stream 0, `origin`
+    /// names the origin the alternatives apply to; on any other stream it
+    /// must be empty and the stream's own origin is meant. `field_value` uses
+    /// the `alt-svc` header syntax, e.g. from `AltSvc::to_field_value`.
+    ///
+    /// # Panics
+    /// Panics if `origin` is empty on stream 0 or non-empty on another stream,
+    /// is not ASCII or longer than 65,535 bytes, or the frame would exceed the
+    /// maximum 24-bit frame length (16,777,215).
+    pub fn create_altsvc_frame(stream_id: u32, origin: &str, field_value: &[u8]) -> Vec<u8> {
+        let stream_id = stream_id & 0x7FFFFFFF; // Clear reserved bit
+        if stream_id == 0 {
+            assert!(!origin.is_empty(), "ALTSVC on stream 0 requires an origin");
+        } else {
+            assert!(origin.is_empty(), "ALTSVC on stream {} must not carry an origin", stream_id);
+        }
+        assert!(origin.is_ascii(), "Origin {:?} is not ASCII", origin);
+        let origin_len = u16::try_from(origin.len()).expect("origin longer than 65535 bytes");
+        let length = 2 + origin.len() + field_value.len();
+        assert!(
+            length <= MAX_FRAME_PAYLOAD_LENGTH as usize,
+            "Alt-Svc field value length {} exceeds maximum frame payload length {}",
+            length, MAX_FRAME_PAYLOAD_LENGTH
+        );
+        let mut frame = Vec::with_capacity(9 + length);
+        frame.push((length >> 16) as u8);
+        frame.push((length >> 8) as u8);
+        frame.push(length as u8);
+        frame.push(frame_type::ALTSVC);
+        frame.push(0x0);
+        frame.extend_from_slice(&stream_id.to_be_bytes());
+        frame.extend_from_slice(&origin_len.to_be_bytes());
+        frame.extend_from_slice(origin.as_bytes());
+        frame.extend_from_slice(field_value);
+        frame
+    }
+
+    /// Create an ORIGIN frame (RFC 8336) announcing the server's Origin Set,
+    /// e.g. `["https://example.com", "https://cdn.example.com"]`. An empty
+    /// list clears the set to nothing beyond the connection's own origin.
+    ///
+    /// # Panics
+    /// Panics if an origin is not ASCII or longer than 65,535 bytes, or the
+    /// frame would exceed the maximum 24-bit frame length (16,777,215).
+    pub fn create_origin_frame(origins: &[&str]) -> Vec<u8> {
+        let length: usize = origins.iter().map(|o| 2 + o.len()).sum();
+        assert!(
+            length <= MAX_FRAME_PAYLOAD_LENGTH as usize,
+            "Origin set length {} exceeds maximum frame payload length {}",
+            length, MAX_FRAME_PAYLOAD_LENGTH
+        );
+        let mut frame = Vec::with_capacity(9 + length);
+        frame.push((length >> 16) as u8);
+        frame.push((length >> 8) as u8);
+        frame.push(length as u8);
+        frame.push(frame_type::ORIGIN);
+        frame.push(0x0);
+        frame.extend_from_slice(&[0, 0, 0, 0]); // Stream ID: 0
+        for origin in origins {
+            assert!(origin.is_ascii(), "Origin {:?} is not ASCII", origin);
+            let len = u16::try_from(origin.len()).expect("origin longer than 65535 bytes");
+            frame.extend_from_slice(&len.to_be_bytes());
+            frame.extend_from_slice(origin.as_bytes());
+        }
+        frame
+    }
+
+    /// Create a PRIORITY_UPDATE frame (RFC 9218 Section 7.1) carrying a
+    /// Priority Field Value such as `u=1, i` for `prioritized_stream_id`.
+    ///
+    /// # Panics
+    /// Panics if `prioritized_stream_id` is 0 or the frame would exceed the
+    /// maximum 24-bit frame length (16,777,215).
+    pub fn create_priority_update_frame(prioritized_stream_id: u32, field_value: &[u8]) -> Vec<u8> {
+        let prioritized_stream_id = prioritized_stream_id & 0x7FFFFFFF; // Clear reserved bit
+        assert!(prioritized_stream_id != 0, "PRIORITY_UPDATE requires a non-zero prioritized stream ID");
+        let length = 4 + field_value.len();
+        assert!(
+            length <= MAX_FRAME_PAYLOAD_LENGTH as usize,
+            "Priority field value length {} exceeds maximum frame payload length {}",
+            length, MAX_FRAME_PAYLOAD_LENGTH
+        );
+        let mut frame = Vec::with_capacity(9 + length);
+        frame.push((length >> 16) as u8);
+        frame.push((length >> 8) as u8);
+        frame.push(length as u8);
+        frame.push(frame_type::PRIORITY_UPDATE);
+        frame.push(0x0);
+        frame.extend_from_slice(&[0, 0, 0, 0]); // Stream ID: 0
+        frame.extend_from_slice(&prioritized_stream_id.to_be_bytes());
+        frame.extend_from_slice(field_value);
+        frame
+    }
+
     /// Create HEADERS frame(s) from a pre-encoded HPACK header block,
     /// splitting into HEADERS + CONTINUATION frames if the block exceeds `max_frame_size`.
     ///
@@ -861,6 +1873,49 @@ impl H2Codec {
         frames
     }
 
+    /// Create PUSH_PROMISE frame(s) promising `promised_stream_id` on
+    /// `stream_id`, splitting `header_block` into PUSH_PROMISE + CONTINUATION
+    /// frames if it does not fit in `max_frame_size` together with the
+    /// 4-byte promised stream ID.
+    ///
+    /// # Panics
+    /// Panics if `max_frame_size` exceeds the maximum 24-bit frame length
+    /// (16,777,215) or leaves no room for the promised stream ID (< 5).
+    pub fn create_push_promise_frames(stream_id: u32, promised_stream_id: u32, header_block: &[u8], max_frame_size: u32) -> Vec<Vec<u8>> {
+        assert!(
+            max_frame_size <= MAX_FRAME_PAYLOAD_LENGTH,
+            "max_frame_size {} exceeds maximum frame payload length {}",
+            max_frame_size, MAX_FRAME_PAYLOAD_LENGTH
+        );
+        assert!(max_frame_size > 4, "max_frame_size {} leaves no room for the header block", max_frame_size);
+        let stream_id = stream_id & 0x7FFFFFFF; // Clear reserved bit
+        let promised_stream_id = promised_stream_id & 0x7FFFFFFF;
+
+        let first_len = header_block.len().min(max_frame_size as usize - 4);
+        let (first, mut remaining) = header_block.split_at(first_len);
+        let length = 4 + first.len();
+        let flags_byte = if remaining.is_empty() { flags::END_HEADERS } else { 0x0 };
+
+        let mut frame = Vec::with_capacity(9 + length);
+        frame.push((length >> 16) as u8);
+        frame.push((length >> 8) as u8);
+        frame.push(length as u8);
+        frame.push(frame_type::PUSH_PROMISE);
+        frame.push(flags_byte);
+        frame.extend_from_slice(&stream_id.to_be_bytes());
+        frame.extend_from_slice(&promised_stream_id.to_be_bytes());
+        frame.extend_from_slice(first);
+
+        let mut frames = vec![frame];
+        while !remaining.is_empty() {
+            let chunk_size = remaining.len().min(max_frame_size as usize);
+            let (chunk, rest) = remaining.split_at(chunk_size);
+            remaining = rest;
+            frames.push(Self::create_continuation_frame(stream_id, chunk, remaining.is_empty()));
+        }
+        frames
+    }
+
     /// Create DATA frame(s), splitting into multiple frames if `data` exceeds `max_frame_size`.
     /// END_STREAM is set only on the last frame (when `end_stream` is true).
     ///
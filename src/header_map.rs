@@ -0,0 +1,212 @@
+//! Ordered, case-insensitive, multi-value header map
+//!
+//! [`HeaderMap`] wraps the `Vec<H2Header>` used by `HpackEncoder` and
+//! `HpackDecoder` with indexed lookup by name. Pseudo-header fields are kept
+//! apart from regular fields and always come first in wire order, whatever
+//! order they were inserted in; within each group, insertion order is kept.
+
+use std::collections::HashMap;
+
+use crate::hpack::H2Header;
+
+/// Header fields indexed by name, ignoring ASCII case.
+///
+/// Names are kept exactly as given, so a decoded list with uppercase names
+/// (malformed in HTTP/2, RFC 9113 Section 8.2.1) still fails
+/// `validate_headers` after a round trip through the map. Lookups, `insert`
+/// and `remove` ignore ASCII case.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeaderMap {
+    pseudo: Vec<H2Header>,
+    regular: Vec<H2Header>,
+    /// Positions in `regular` of each name, lowercased, in insertion order.
+    index: HashMap<Vec<u8>, Vec<usize>>,
+}
+
+impl HeaderMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a map from a header list, e.g. the output of `HpackDecoder::decode`.
+    pub fn from_headers(headers: Vec<H2Header>) -> Self {
+        let mut map = Self::new();
+        for h in headers {
+            map.append(h.name, h.value);
+        }
+        map
+    }
+
+    /// Convert into a header list in wire order (pseudo-headers first),
+    /// ready for `HpackEncoder::encode`.
+    pub fn into_headers(self) -> Vec<H2Header> {
+        let mut out = self.pseudo;
+        out.extend(self.regular);
+        out
+    }
+
+    /// Number of fields, counting each value separately.
+    pub fn len(&self) -> usize {
+        self.pseudo.len() + self.regular.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add a field, keeping any existing values for the same name.
+    pub fn append(&mut self, name: impl Into<Vec<u8>>, value: impl Into<Vec<u8>>) {
+        let name = name.into();
+        if name.starts_with(b":") {
+            self.pseudo.push(H2Header::new(name, value));
+        } else {
+            self.index.entry(name.to_ascii_lowercase()).or_default().push(self.regular.len());
+            self.regular.push(H2Header::new(name, value));
+        }
+    }
+
+    /// Set a field, replacing all existing values for the same name. The new
+    /// value takes the position, and keeps the name, of the first one it
+    /// replaces.
+    pub fn insert(&mut self, name: impl Into<Vec<u8>>, value: impl Into<Vec<u8>>) {
+        let name = name.into();
+        let value = value.into();
+        let first = if name.starts_with(b":") {
+            self.pseudo.iter().position(|h| h.name.eq_ignore_ascii_case(&name))
+        } else {
+            self.index.get(&name.to_ascii_lowercase()).map(|positions| positions[0])
+        };
+        match first {
+            Some(pos) => {
+                let slot = if name.starts_with(b":") { &mut self.pseudo[pos] } else { &mut self.regular[pos] };
+                slot.value = value;
+                self.remove_after(&name, pos);
+            }
+            None => self.append(name, value),
+        }
+    }
+
+    /// First value for `name`, if any.
+    pub fn get(&self, name: impl AsRef<[u8]>) -> Option<&[u8]> {
+        self.get_all(name).next()
+    }
+
+    /// All values for `name`, in insertion order.
+    pub fn get_all(&self, name: impl AsRef<[u8]>) -> impl Iterator<Item = &[u8]> {
+        let name = name.as_ref().to_ascii_lowercase();
+        let (pseudo, regular): (&[H2Header], &[usize]) = if name.starts_with(b":") {
+            (&self.pseudo, &[])
+        } else {
+            (&[], self.index.get(&name).map_or(&[][..], |positions| positions))
+        };
+        pseudo
+            .iter()
+            .filter(move |h| h.name.eq_ignore_ascii_case(&name))
+            .map(|h| h.value.as_slice())
+            .chain(regular.iter().map(|&pos| self.regular[pos].value.as_slice()))
+    }
+
+    pub fn contains(&self, name: impl AsRef<[u8]>) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Remove all values for `name`, returning them in insertion order.
+    pub fn remove(&mut self, name: impl AsRef<[u8]>) -> Vec<Vec<u8>> {
+        let name = name.as_ref().to_ascii_lowercase();
+        let pseudo = name.starts_with(b":");
+        if !pseudo && !self.index.contains_key(&name) {
+            return Vec::new();
+        }
+        let list = if pseudo { &mut self.pseudo } else { &mut self.regular };
+        let (removed, kept): (Vec<H2Header>, Vec<H2Header>) =
+            std::mem::take(list).into_iter().partition(|h| h.name.eq_ignore_ascii_case(&name));
+        *list = kept;
+        if !pseudo {
+            self.reindex();
+        }
+        removed.into_iter().map(|h| h.value).collect()
+    }
+
+    /// Iterate over all fields in wire order: pseudo-headers, then regular
+    /// fields, each in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &H2Header> {
+        self.pseudo.iter().chain(self.regular.iter())
+    }
+
+    /// Iterate over the pseudo-header fields only.
+    pub fn pseudo_headers(&self) -> impl Iterator<Item = &H2Header> {
+        self.pseudo.iter()
+    }
+
+    /// Iterate over the regular fields only.
+    pub fn regular_headers(&self) -> impl Iterator<Item = &H2Header> {
+        self.regular.iter()
+    }
+
+    /// Remove every value for `name` after position `pos` of its group.
+    fn remove_after(&mut self, name: &[u8], pos: usize) {
+        let list = if name.starts_with(b":") { &mut self.pseudo } else { &mut self.regular };
+        let mut i = 0;
+        list.retain(|h| {
+            let keep = i <= pos || !h.name.eq_ignore_ascii_case(name);
+            i += 1;
+            keep
+        });
+        if !name.starts_with(b":") {
+            self.reindex();
+        }
+    }
+
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (pos, h) in self.regular.iter().enumerate() {
+            self.index.entry(h.name.to_ascii_lowercase()).or_default().push(pos);
+        }
+    }
+}
+
+impl From<Vec<H2Header>> for HeaderMap {
+    fn from(headers: Vec<H2Header>) -> Self {
+        Self::from_headers(headers)
+    }
+}
+
+impl From<HeaderMap> for Vec<H2Header> {
+    fn from(map: HeaderMap) -> Self {
+        map.into_headers()
+    }
+}
+
+impl FromIterator<H2Header> for HeaderMap {
+    fn from_iter<I: IntoIterator<Item = H2Header>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl Extend<H2Header> for HeaderMap {
+    fn extend<I: IntoIterator<Item = H2Header>>(&mut self, iter: I) {
+        for h in iter {
+            self.append(h.name, h.value);
+        }
+    }
+}
+
+impl IntoIterator for HeaderMap {
+    type Item = H2Header;
+    type IntoIter = std::vec::IntoIter<H2Header>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_headers().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a HeaderMap {
+    type Item = &'a H2Header;
+    type IntoIter = std::iter::Chain<std::slice::Iter<'a, H2Header>, std::slice::Iter<'a, H2Header>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.pseudo.iter().chain(self.regular.iter())
+    }
+}
@@ -0,0 +1,62 @@
+//! Cookie crumb splitting and joining (RFC 9113 Section 8.2.3).
+//!
+//! A `cookie` field may be split into one field per cookie-pair ("crumb") so
+//! that unchanged crumbs compress to a single index. Before the cookies are
+//! handed to HTTP/1.1 or any other non-HTTP/2 context, the crumbs must be
+//! joined back into one field with `"; "` between them.
+
+use super::H2Header;
+
+/// Split every `cookie` field into one field per cookie-pair.
+///
+/// Other fields are copied unchanged and keep their order; crumbs take the
+/// place of the field they came from. Empty crumbs are dropped.
+pub fn split_cookies(headers: &[H2Header]) -> Vec<H2Header> {
+    let mut out = Vec::with_capacity(headers.len());
+    for h in headers {
+        if h.name == b"cookie" {
+            out.extend(crumbs(&h.value).map(|crumb| H2Header::new("cookie", crumb)));
+        } else {
+            out.push(h.clone());
+        }
+    }
+    out
+}
+
+/// Join all `cookie` fields into a single field, separated by `"; "`.
+///
+/// The joined field takes the position of the first `cookie` field; other
+/// fields keep their order. Lists with at most one `cookie` field are
+/// returned unchanged.
+pub fn join_cookies(headers: Vec<H2Header>) -> Vec<H2Header> {
+    if headers.iter().filter(|h| h.name == b"cookie").count() < 2 {
+        return headers;
+    }
+    let mut out = Vec::with_capacity(headers.len());
+    let mut joined: Option<usize> = None;
+    for h in headers {
+        if h.name != b"cookie" {
+            out.push(h);
+            continue;
+        }
+        match joined {
+            Some(i) => {
+                out[i].value.extend_from_slice(b"; ");
+                out[i].value.extend_from_slice(&h.value);
+            }
+            None => {
+                joined = Some(out.len());
+                out.push(h);
+            }
+        }
+    }
+    out
+}
+
+/// The cookie-pairs of a `cookie` value, without separators or padding.
+pub(super) fn crumbs(value: &[u8]) -> impl Iterator<Item = &[u8]> {
+    value
+        .split(|&b| b == b';')
+        .map(|crumb| crumb.trim_ascii())
+        .filter(|crumb| !crumb.is_empty())
+}
@@ -0,0 +1,421 @@
+//! HPACK header block decoder.
+
+use super::huffman::{self, HuffmanError};
+use super::table::{self, DynamicTable, TableEntry, DEFAULT_TABLE_SIZE};
+use super::H2Header;
+use crate::h2_codec::error_code;
+use crate::snapshot::{SnapshotError, SnapshotReader, SnapshotWriter};
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"HPKD";
+const SNAPSHOT_VERSION: u8 = 1;
+
+/// A decoded header field together with how it was represented on the wire.
+///
+/// Returned by [`HpackDecoder::decode_fields`] for diagnostics; use
+/// [`HpackDecoder::decode`] when only the headers are needed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedField {
+    pub header: H2Header,
+    /// True if the name was sent as a Huffman-coded string literal.
+    /// Always false when the name was taken from the indexing tables.
+    pub name_huffman: bool,
+    /// True if the value was sent as a Huffman-coded string literal.
+    /// Always false for fully indexed fields.
+    pub value_huffman: bool,
+}
+
+/// The kind of failure reported by [`HpackError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HpackErrorKind {
+    /// An index referenced neither the static nor the dynamic table.
+    InvalidIndex(usize),
+    /// A prefixed integer did not fit in 32 bits.
+    IntegerOverflow,
+    /// Huffman padding was longer than 7 bits or not the prefix of EOS.
+    InvalidHuffmanPadding,
+    /// The Huffman EOS symbol appeared inside a string literal.
+    HuffmanEos,
+    /// A dynamic table size update followed a header field (RFC 7541
+    /// Section 4.2).
+    TableSizeUpdatePosition,
+    /// The block ended in the middle of a field representation.
+    Truncated,
+    /// A dynamic table size update exceeded the allowed maximum.
+    LimitExceeded,
+    /// The decoded header list exceeded the configured maximum size
+    /// (see [`HpackDecoder::set_max_header_list_size`]).
+    HeaderListTooLarge,
+}
+
+/// A header block decoding failure.
+///
+/// Any HPACK error leaves the decoder's dynamic table out of sync with the
+/// peer; the connection must be closed with COMPRESSION_ERROR
+/// (RFC 7540 Section 4.3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HpackError {
+    pub kind: HpackErrorKind,
+    /// Byte offset, within the header block, of the field representation
+    /// that failed to decode.
+    pub offset: usize,
+}
+
+impl HpackError {
+    /// HTTP/2 error code to send in GOAWAY (always COMPRESSION_ERROR).
+    pub fn error_code(&self) -> u32 {
+        error_code::COMPRESSION_ERROR
+    }
+}
+
+impl std::fmt::Display for HpackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HPACK decode error at offset {}: ", self.offset)?;
+        match self.kind {
+            HpackErrorKind::InvalidIndex(index) => write!(f, "invalid table index {}", index),
+            HpackErrorKind::IntegerOverflow => write!(f, "integer overflow"),
+            HpackErrorKind::InvalidHuffmanPadding => write!(f, "invalid Huffman padding"),
+            HpackErrorKind::HuffmanEos => write!(f, "EOS symbol in Huffman string"),
+            HpackErrorKind::TableSizeUpdatePosition => write!(f, "dynamic table size update not at start of block"),
+            HpackErrorKind::Truncated => write!(f, "truncated field representation"),
+            HpackErrorKind::LimitExceeded => write!(f, "dynamic table size update exceeds limit"),
+            HpackErrorKind::HeaderListTooLarge => write!(f, "header list exceeds maximum size"),
+        }
+    }
+}
+
+impl std::error::Error for HpackError {}
+
+impl From<HuffmanError> for HpackErrorKind {
+    fn from(e: HuffmanError) -> Self {
+        match e {
+            HuffmanError::EosInString => HpackErrorKind::HuffmanEos,
+            HuffmanError::PaddingTooLong | HuffmanError::InvalidPadding => HpackErrorKind::InvalidHuffmanPadding,
+        }
+    }
+}
+
+/// HPACK decoder for HTTP/2 header blocks.
+///
+/// Maintains the dynamic table state for one direction of a connection, so a
+/// single instance must see every header block sent by the peer, in order.
+pub struct HpackDecoder {
+    table: DynamicTable,
+    /// Largest size a dynamic table size update may set: the
+    /// SETTINGS_HEADER_TABLE_SIZE value we advertised.
+    max_allowed_table_size: usize,
+    /// Largest decoded header list (SETTINGS_MAX_HEADER_LIST_SIZE), if any.
+    max_header_list_size: Option<usize>,
+    /// Progress through a header block fed in fragments.
+    block: BlockProgress,
+}
+
+/// State carried between `decode_fragment` calls for one header block.
+#[derive(Debug, Default)]
+struct BlockProgress {
+    /// Offset of `partial[0]` within the header block.
+    offset: usize,
+    /// Bytes of a field representation split across fragments.
+    partial: Vec<u8>,
+    /// True once a header field (not a size update) has been decoded.
+    has_fields: bool,
+    /// Header list size so far (RFC 7540 Section 6.5.2).
+    list_size: usize,
+}
+
+impl std::fmt::Debug for HpackDecoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HpackDecoder").finish()
+    }
+}
+
+impl Default for HpackDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HpackDecoder {
+    pub fn new() -> Self {
+        Self {
+            table: DynamicTable::default(),
+            max_allowed_table_size: DEFAULT_TABLE_SIZE,
+            max_header_list_size: None,
+            block: BlockProgress::default(),
+        }
+    }
+
+    /// Set the largest dynamic table size the peer may select with a size
+    /// update. Call this with the SETTINGS_HEADER_TABLE_SIZE value advertised
+    /// to the peer (default 4096). Larger updates fail with
+    /// [`HpackErrorKind::LimitExceeded`].
+    pub fn set_max_allowed_table_size(&mut self, size: usize) {
+        self.max_allowed_table_size = size;
+    }
+
+    /// Limit the decoded header list size, counted as the sum of
+    /// `name + value + 32` over all fields (RFC 7540 Section 6.5.2).
+    /// Decoding stops with [`HpackErrorKind::HeaderListTooLarge`] as soon as
+    /// the limit is crossed. `None` (the default) disables the check.
+    pub fn set_max_header_list_size(&mut self, size: Option<usize>) {
+        self.max_header_list_size = size;
+    }
+
+    /// Decode an HPACK-encoded header block into H2Headers.
+    ///
+    /// Header names and values are returned as raw bytes to preserve
+    /// binary content faithfully (no lossy UTF-8 conversion).
+    ///
+    /// `data` is always a complete block: a block left unfinished by
+    /// [`HpackDecoder::decode_fragment`] is abandoned, along with any bytes
+    /// it had buffered.
+    pub fn decode(&mut self, data: &[u8]) -> Result<Vec<H2Header>, HpackError> {
+        Ok(self
+            .decode_fields(data)?
+            .into_iter()
+            .map(|field| field.header)
+            .collect())
+    }
+
+    /// Decode an HPACK-encoded header block, reporting for each field whether
+    /// its name and value were Huffman-coded. Like [`HpackDecoder::decode`],
+    /// this starts a new block.
+    pub fn decode_fields(&mut self, data: &[u8]) -> Result<Vec<DecodedField>, HpackError> {
+        self.block = BlockProgress::default();
+        let mut fields = Vec::new();
+        self.decode_part(data, true, &mut fields)?;
+        Ok(fields)
+    }
+
+    /// Decode one fragment of a header block, returning the fields completed
+    /// so far. Pass `end_of_block` for the last fragment (the HEADERS or
+    /// CONTINUATION frame carrying END_HEADERS).
+    ///
+    /// Fields are emitted as soon as their representation is complete; only
+    /// a representation split across fragments is buffered. This lets the
+    /// header list limit be enforced before the whole block has arrived.
+    /// Error offsets are relative to the start of the whole block.
+    ///
+    /// A block's fragments must be fed in order with no other block in
+    /// between, which HTTP/2 guarantees (RFC 7540 Section 6.10).
+    pub fn decode_fragment(&mut self, fragment: &[u8], end_of_block: bool) -> Result<Vec<H2Header>, HpackError> {
+        let mut fields = Vec::new();
+        self.decode_part(fragment, end_of_block, &mut fields)?;
+        Ok(fields.into_iter().map(|field| field.header).collect())
+    }
+
+    /// Shared implementation of block and fragment decoding. Resets the block
+    /// progress on error or at the end of the block.
+    fn decode_part(&mut self, fragment: &[u8], end_of_block: bool, out: &mut Vec<DecodedField>) -> Result<(), HpackError> {
+        let result = self.decode_part_inner(fragment, end_of_block, out);
+        if result.is_err() || end_of_block {
+            self.block = BlockProgress::default();
+        }
+        result
+    }
+
+    fn decode_part_inner(&mut self, fragment: &[u8], end_of_block: bool, out: &mut Vec<DecodedField>) -> Result<(), HpackError> {
+        let joined;
+        let buf: &[u8] = if self.block.partial.is_empty() {
+            fragment
+        } else {
+            let mut partial = std::mem::take(&mut self.block.partial);
+            partial.extend_from_slice(fragment);
+            joined = partial;
+            &joined
+        };
+        let base = self.block.offset;
+        let mut pos = 0;
+        while pos < buf.len() {
+            let start = pos;
+            let fail = |kind| HpackError { kind, offset: base + start };
+            match self.decode_field(buf, &mut pos) {
+                Ok(Some(field)) => {
+                    self.block.list_size += table::entry_size(&field.header.name, &field.header.value);
+                    if self.max_header_list_size.is_some_and(|max| self.block.list_size > max) {
+                        return Err(fail(HpackErrorKind::HeaderListTooLarge));
+                    }
+                    self.block.has_fields = true;
+                    out.push(field);
+                }
+                // RFC 7541 Section 4.2: size updates must come first in the block.
+                Ok(None) if self.block.has_fields => return Err(fail(HpackErrorKind::TableSizeUpdatePosition)),
+                Ok(None) => {}
+                // The rest of this representation is in a later fragment.
+                // Truncation is detected before any table state changes.
+                Err(HpackErrorKind::Truncated) if !end_of_block => {
+                    pos = start;
+                    break;
+                }
+                Err(kind) => return Err(fail(kind)),
+            }
+        }
+        self.block.partial = buf[pos..].to_vec();
+        self.block.offset = base + pos;
+        Ok(())
+    }
+
+    /// List the dynamic table entries in index order (newest first, index 62).
+    ///
+    /// Intended for debugging: compare a dump with the peer's view when a
+    /// COMPRESSION_ERROR occurs.
+    pub fn dynamic_table(&self) -> Vec<TableEntry<'_>> {
+        self.table.entries()
+    }
+
+    /// Current dynamic table size in octets (RFC 7541 Section 4.1).
+    pub fn dynamic_table_size(&self) -> usize {
+        self.table.size()
+    }
+
+    /// Maximum dynamic table size in octets currently in effect.
+    pub fn max_dynamic_table_size(&self) -> usize {
+        self.table.max_size()
+    }
+
+    /// Export the decoder state (dynamic table, size limits and any header
+    /// block partially fed through [`HpackDecoder::decode_fragment`]) so an
+    /// identical decoder can be rebuilt with [`HpackDecoder::restore`],
+    /// e.g. after migrating a suspended connection to another worker.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut w = SnapshotWriter::new(SNAPSHOT_MAGIC, SNAPSHOT_VERSION);
+        w.u64(self.max_allowed_table_size as u64);
+        w.bool(self.max_header_list_size.is_some());
+        w.u64(self.max_header_list_size.unwrap_or(0) as u64);
+        self.table.write_snapshot(&mut w);
+        w.u64(self.block.offset as u64);
+        w.bytes(&self.block.partial);
+        w.bool(self.block.has_fields);
+        w.u64(self.block.list_size as u64);
+        w.finish()
+    }
+
+    /// Rebuild a decoder from the output of [`HpackDecoder::snapshot`].
+    pub fn restore(snapshot: &[u8]) -> Result<Self, SnapshotError> {
+        let mut r = SnapshotReader::new(snapshot, SNAPSHOT_MAGIC, SNAPSHOT_VERSION)?;
+        let max_allowed_table_size = r.usize()?;
+        let has_list_limit = r.bool()?;
+        let list_limit = r.usize()?;
+        let table = DynamicTable::read_snapshot(&mut r)?;
+        let offset = r.usize()?;
+        let partial = r.bytes()?.to_vec();
+        let has_fields = r.bool()?;
+        let list_size = r.usize()?;
+        r.finish()?;
+        Ok(Self {
+            table,
+            max_allowed_table_size,
+            max_header_list_size: has_list_limit.then_some(list_limit),
+            block: BlockProgress {
+                offset,
+                partial,
+                has_fields,
+                list_size,
+            },
+        })
+    }
+
+    /// Decode one field representation starting at `*pos`, advancing `*pos`
+    /// past it. Returns `None` for a dynamic table size update.
+    fn decode_field(&mut self, buf: &[u8], pos: &mut usize) -> Result<Option<DecodedField>, HpackErrorKind> {
+        let first = buf[*pos];
+        if first & 0x80 != 0 {
+            // Indexed Header Field (Section 6.1)
+            let index = decode_integer(buf, pos, 7)?;
+            let (name, value) = table::get(&self.table, index).ok_or(HpackErrorKind::InvalidIndex(index))?;
+            return Ok(Some(DecodedField {
+                header: H2Header::new(name, value),
+                name_huffman: false,
+                value_huffman: false,
+            }));
+        }
+        if first & 0xE0 == 0x20 {
+            // Dynamic Table Size Update (Section 6.3)
+            let max_size = decode_integer(buf, pos, 5)?;
+            if max_size > self.max_allowed_table_size {
+                return Err(HpackErrorKind::LimitExceeded);
+            }
+            self.table.set_max_size(max_size);
+            return Ok(None);
+        }
+
+        // Literal Header Field with Incremental Indexing (Section 6.2.1) uses a
+        // 6-bit prefix; without indexing / never indexed (6.2.2, 6.2.3) use 4 bits.
+        let indexing = first & 0x40 != 0;
+        let prefix = if indexing { 6 } else { 4 };
+        // Octets the name and value may still take under the header list
+        // limit, so oversized literals are rejected from their declared
+        // length before a fragmented block buffers them.
+        let budget = self
+            .max_header_list_size
+            .map(|max| max.saturating_sub(self.block.list_size).saturating_sub(table::ENTRY_OVERHEAD));
+        let name_index = decode_integer(buf, pos, prefix)?;
+        let (name, name_huffman) = if name_index == 0 {
+            decode_string(buf, pos, budget)?
+        } else {
+            let (name, _) = table::get(&self.table, name_index).ok_or(HpackErrorKind::InvalidIndex(name_index))?;
+            (name.to_vec(), false)
+        };
+        let (value, value_huffman) = decode_string(buf, pos, budget.map(|b| b.saturating_sub(name.len())))?;
+        if indexing {
+            self.table.insert(name.clone(), value.clone());
+        }
+        Ok(Some(DecodedField {
+            header: H2Header::new(name, value),
+            name_huffman,
+            value_huffman,
+        }))
+    }
+}
+
+/// Decode a prefixed integer (RFC 7541 Section 5.1) starting at `*pos`.
+fn decode_integer(buf: &[u8], pos: &mut usize, prefix_bits: u8) -> Result<usize, HpackErrorKind> {
+    let mask = ((1u16 << prefix_bits) - 1) as u8;
+    let first = *buf.get(*pos).ok_or(HpackErrorKind::Truncated)? & mask;
+    *pos += 1;
+    if first < mask {
+        return Ok(first as usize);
+    }
+
+    let mut value = mask as u64;
+    let mut shift = 0u32;
+    loop {
+        let byte = *buf.get(*pos).ok_or(HpackErrorKind::Truncated)?;
+        *pos += 1;
+        value += ((byte & 0x7F) as u64) << shift;
+        // Values are bounded by 32 bits: nothing in HPACK (lengths, indices,
+        // table sizes) legitimately needs more.
+        if value > u32::MAX as u64 {
+            return Err(HpackErrorKind::IntegerOverflow);
+        }
+        if byte & 0x80 == 0 {
+            return usize::try_from(value).map_err(|_| HpackErrorKind::IntegerOverflow);
+        }
+        shift += 7;
+        if shift > 28 {
+            return Err(HpackErrorKind::IntegerOverflow);
+        }
+    }
+}
+
+/// Decode a string literal (RFC 7541 Section 5.2) starting at `*pos`.
+/// Returns the decoded octets and whether they were Huffman-coded.
+///
+/// Fails with `HeaderListTooLarge` as soon as the declared length is known
+/// if the string cannot fit in `max_len` decoded octets. Huffman codes are
+/// at most 30 bits, so `len` coded octets decode to at least `len * 8 / 30`.
+fn decode_string(buf: &[u8], pos: &mut usize, max_len: Option<usize>) -> Result<(Vec<u8>, bool), HpackErrorKind> {
+    let huffman = *buf.get(*pos).ok_or(HpackErrorKind::Truncated)? & 0x80 != 0;
+    let len = decode_integer(buf, pos, 7)?;
+    let min_decoded = if huffman { len / 30 * 8 } else { len };
+    if max_len.is_some_and(|max| min_decoded > max) {
+        return Err(HpackErrorKind::HeaderListTooLarge);
+    }
+    let end = pos.checked_add(len).ok_or(HpackErrorKind::Truncated)?;
+    let raw = buf.get(*pos..end).ok_or(HpackErrorKind::Truncated)?;
+    *pos = end;
+    if huffman {
+        huffman::decode(raw).map(|s| (s, true)).map_err(HpackErrorKind::from)
+    } else {
+        Ok((raw.to_vec(), false))
+    }
+}
@@ -0,0 +1,274 @@
+//! HPACK header block encoder.
+
+use super::cookie;
+use super::huffman;
+use super::policy::{DefaultIndexingPolicy, FieldMatch, IndexingDecision, IndexingPolicy};
+use super::table::{self, DynamicTable, TableEntry, TableMatch};
+use super::H2Header;
+use crate::snapshot::{SnapshotError, SnapshotReader, SnapshotWriter};
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"HPKE";
+const SNAPSHOT_VERSION: u8 = 1;
+
+/// Controls when the encoder Huffman-codes string literals
+/// (RFC 7541 Section 5.2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HuffmanStrategy {
+    /// Never Huffman-code; literals are sent as raw octets. Output is
+    /// byte-for-byte predictable, which helps when debugging captures.
+    #[default]
+    Never,
+    /// Always Huffman-code, even when it makes the literal longer.
+    Always,
+    /// Huffman-code a literal only when the result is strictly shorter.
+    Shorter,
+}
+
+/// HPACK encoder for HTTP/2 header blocks.
+///
+/// Maintains the dynamic table state for one direction of a connection, so a
+/// single instance must produce every header block sent to the peer, in order.
+pub struct HpackEncoder {
+    table: DynamicTable,
+    huffman: HuffmanStrategy,
+    policy: Box<dyn IndexingPolicy>,
+    split_cookies: bool,
+}
+
+impl std::fmt::Debug for HpackEncoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HpackEncoder")
+            .field("huffman", &self.huffman)
+            .field("split_cookies", &self.split_cookies)
+            .finish()
+    }
+}
+
+impl Default for HpackEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HpackEncoder {
+    pub fn new() -> Self {
+        Self {
+            table: DynamicTable::default(),
+            huffman: HuffmanStrategy::default(),
+            policy: Box::new(DefaultIndexingPolicy),
+            split_cookies: false,
+        }
+    }
+
+    /// Create an encoder using the given Huffman strategy.
+    pub fn with_huffman_strategy(strategy: HuffmanStrategy) -> Self {
+        Self {
+            huffman: strategy,
+            ..Self::new()
+        }
+    }
+
+    /// Change the Huffman strategy for subsequent `encode` calls.
+    ///
+    /// This only affects how literals are written; the decoder handles both
+    /// forms, so it can be switched at any point in a connection.
+    pub fn set_huffman_strategy(&mut self, strategy: HuffmanStrategy) {
+        self.huffman = strategy;
+    }
+
+    /// Return the current Huffman strategy.
+    pub fn huffman_strategy(&self) -> HuffmanStrategy {
+        self.huffman
+    }
+
+    /// List the dynamic table entries in index order (newest first, index 62).
+    ///
+    /// Intended for debugging: compare a dump with the peer decoder's view when a
+    /// COMPRESSION_ERROR occurs.
+    pub fn dynamic_table(&self) -> Vec<TableEntry<'_>> {
+        self.table.entries()
+    }
+
+    /// Current dynamic table size in octets (RFC 7541 Section 4.1).
+    pub fn dynamic_table_size(&self) -> usize {
+        self.table.size()
+    }
+
+    /// Maximum dynamic table size in octets currently in effect.
+    pub fn max_dynamic_table_size(&self) -> usize {
+        self.table.max_size()
+    }
+
+    /// Split `cookie` fields into one field per cookie-pair when encoding
+    /// (RFC 9113 Section 8.2.3). Off by default.
+    ///
+    /// Crumbs only compress well if they enter the dynamic table; the
+    /// default indexing policy leaves `cookie` (a static table name) out, so
+    /// pair this with a policy that indexes cookie crumbs.
+    pub fn set_split_cookies(&mut self, enabled: bool) {
+        self.split_cookies = enabled;
+    }
+
+    /// Whether `cookie` fields are split into crumbs when encoding.
+    pub fn split_cookies(&self) -> bool {
+        self.split_cookies
+    }
+
+    /// Export the encoder state (dynamic table, size limit, Huffman
+    /// strategy and cookie splitting) so an identical encoder can be rebuilt with
+    /// [`HpackEncoder::restore`].
+    ///
+    /// The indexing policy is code, not state, and is not included.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut w = SnapshotWriter::new(SNAPSHOT_MAGIC, SNAPSHOT_VERSION);
+        w.u8(match self.huffman {
+            HuffmanStrategy::Never => 0,
+            HuffmanStrategy::Always => 1,
+            HuffmanStrategy::Shorter => 2,
+        });
+        self.table.write_snapshot(&mut w);
+        w.bool(self.split_cookies);
+        w.finish()
+    }
+
+    /// Rebuild an encoder from the output of [`HpackEncoder::snapshot`].
+    ///
+    /// The restored encoder uses [`DefaultIndexingPolicy`]; reinstall a custom
+    /// policy with [`HpackEncoder::set_indexing_policy`].
+    pub fn restore(snapshot: &[u8]) -> Result<Self, SnapshotError> {
+        let mut r = SnapshotReader::new(snapshot, SNAPSHOT_MAGIC, SNAPSHOT_VERSION)?;
+        let huffman = match r.u8()? {
+            0 => HuffmanStrategy::Never,
+            1 => HuffmanStrategy::Always,
+            2 => HuffmanStrategy::Shorter,
+            _ => return Err(SnapshotError::Invalid("unknown Huffman strategy")),
+        };
+        let table = DynamicTable::read_snapshot(&mut r)?;
+        let split_cookies = r.bool()?;
+        r.finish()?;
+        Ok(Self {
+            table,
+            huffman,
+            policy: Box::new(DefaultIndexingPolicy),
+            split_cookies,
+        })
+    }
+
+    /// Replace the policy deciding which fields enter the dynamic table.
+    ///
+    /// Only affects subsequent `encode` calls; entries already in the table
+    /// stay until evicted.
+    pub fn set_indexing_policy(&mut self, policy: impl IndexingPolicy + 'static) {
+        self.policy = Box::new(policy);
+    }
+
+    /// Encode headers into an HPACK header block.
+    ///
+    /// Each field is looked up in the static and dynamic tables and the
+    /// indexing policy chooses its representation. With the default policy,
+    /// fully indexed fields are sent as an index, fields with a known name as
+    /// a literal without indexing, and fields with a new name as a literal
+    /// added to the dynamic table.
+    pub fn encode(&mut self, headers: &[H2Header]) -> Vec<u8> {
+        let mut dst = Vec::new();
+        for h in headers {
+            if self.split_cookies && h.name == b"cookie" {
+                for crumb in cookie::crumbs(&h.value) {
+                    self.encode_field(&H2Header::new("cookie", crumb), &mut dst);
+                }
+            } else {
+                self.encode_field(h, &mut dst);
+            }
+        }
+        dst
+    }
+
+    fn encode_field(&mut self, h: &H2Header, dst: &mut Vec<u8>) {
+        let found = table::find(Some(&self.table), &h.name, &h.value);
+        let field_match = match found {
+            TableMatch::Full(_) => FieldMatch::Full,
+            TableMatch::Name(_) => FieldMatch::Name,
+            TableMatch::None => FieldMatch::None,
+        };
+        let decision = self.policy.decide(h, field_match);
+        let name_index = match (found, decision) {
+            (TableMatch::Full(index), IndexingDecision::Index | IndexingDecision::NoIndex) => {
+                encode_integer(index, 7, 0x80, dst);
+                return;
+            }
+            (TableMatch::Full(index) | TableMatch::Name(index), _) => index,
+            (TableMatch::None, _) => 0,
+        };
+        match decision {
+            IndexingDecision::Index => encode_integer(name_index, 6, 0x40, dst),
+            IndexingDecision::NoIndex => encode_integer(name_index, 4, 0x00, dst),
+            IndexingDecision::NeverIndex => encode_integer(name_index, 4, 0x10, dst),
+        }
+        if name_index == 0 {
+            encode_string(&h.name, self.huffman, dst);
+        }
+        encode_string(&h.value, self.huffman, dst);
+        if decision == IndexingDecision::Index {
+            self.table.insert(h.name.clone(), h.value.clone());
+        }
+    }
+}
+
+/// Encode headers without any dynamic table state.
+///
+/// Only static table references and literals without indexing are emitted,
+/// and no dynamic table size update is ever written. The resulting block can
+/// be sent at any point in a connection, from any component, without
+/// desynchronizing the peer's decoder from the connection's `HpackEncoder`
+/// (e.g. a fallback error response built outside the main encoder).
+pub fn encode_stateless(headers: &[H2Header], huffman: HuffmanStrategy) -> Vec<u8> {
+    let mut dst = Vec::new();
+    for h in headers {
+        match table::find(None, &h.name, &h.value) {
+            TableMatch::Full(index) => encode_integer(index, 7, 0x80, &mut dst),
+            TableMatch::Name(index) => {
+                encode_integer(index, 4, 0x00, &mut dst);
+                encode_string(&h.value, huffman, &mut dst);
+            }
+            TableMatch::None => {
+                dst.push(0x00);
+                encode_string(&h.name, huffman, &mut dst);
+                encode_string(&h.value, huffman, &mut dst);
+            }
+        }
+    }
+    dst
+}
+
+/// Encode a prefixed integer (RFC 7541 Section 5.1). `flags` holds the bits
+/// above the prefix in the first octet.
+pub(crate) fn encode_integer(value: usize, prefix_bits: u8, flags: u8, dst: &mut Vec<u8>) {
+    let max_prefix = (1usize << prefix_bits) - 1;
+    if value < max_prefix {
+        dst.push(flags | value as u8);
+        return;
+    }
+    dst.push(flags | max_prefix as u8);
+    let mut rest = value - max_prefix;
+    while rest >= 0x80 {
+        dst.push((rest as u8 & 0x7F) | 0x80);
+        rest >>= 7;
+    }
+    dst.push(rest as u8);
+}
+
+/// Encode a string literal (RFC 7541 Section 5.2) per `strategy`.
+pub(crate) fn encode_string(data: &[u8], strategy: HuffmanStrategy, dst: &mut Vec<u8>) {
+    let use_huffman = match strategy {
+        HuffmanStrategy::Never => false,
+        HuffmanStrategy::Always => true,
+        HuffmanStrategy::Shorter => huffman::encoded_len(data) < data.len(),
+    };
+    if use_huffman {
+        encode_integer(huffman::encoded_len(data), 7, 0x80, dst);
+        huffman::encode(data, dst);
+    } else {
+        encode_integer(data.len(), 7, 0x00, dst);
+        dst.extend_from_slice(data);
+    }
+}
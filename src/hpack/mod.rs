@@ -0,0 +1,49 @@
+//! HPACK: Header Compression for HTTP/2 (RFC 7541)
+//!
+//! Provides the H2Header type and the stateful decoder/encoder used
+//! throughout the kernel. Indexing tables and the Huffman code are
+//! implemented in-crate so that encoding strategy and table state are
+//! under our control.
+
+mod cookie;
+mod decoder;
+mod encoder;
+mod huffman;
+mod policy;
+mod table;
+
+pub use cookie::{join_cookies, split_cookies};
+pub use decoder::{DecodedField, HpackDecoder, HpackError, HpackErrorKind};
+pub use encoder::{encode_stateless, HpackEncoder, HuffmanStrategy};
+pub use policy::{DefaultIndexingPolicy, FieldMatch, IndexingDecision, IndexingPolicy};
+pub use table::TableEntry;
+
+/// A decoded HTTP/2 header.
+///
+/// Both `name` and `value` are raw byte vectors to avoid data loss with
+/// non-UTF-8 values (e.g. gRPC binary metadata). Use the convenience
+/// methods `name_str()` / `value_str()` when you know the content is UTF-8.
+#[derive(Debug, Clone, PartialEq)]
+pub struct H2Header {
+    pub name: Vec<u8>,
+    pub value: Vec<u8>,
+}
+
+impl H2Header {
+    pub fn new(name: impl Into<Vec<u8>>, value: impl Into<Vec<u8>>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Return the header name as a UTF-8 string, or an error if not valid UTF-8.
+    pub fn name_str(&self) -> Result<&str, std::str::Utf8Error> {
+        std::str::from_utf8(&self.name)
+    }
+
+    /// Return the header value as a UTF-8 string, or an error if not valid UTF-8.
+    pub fn value_str(&self) -> Result<&str, std::str::Utf8Error> {
+        std::str::from_utf8(&self.value)
+    }
+}
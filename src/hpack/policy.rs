@@ -0,0 +1,69 @@
+//! Indexing policy: which header fields `HpackEncoder` adds to the dynamic table.
+
+use super::H2Header;
+
+/// What the indexing tables already hold for a header field, passed to
+/// [`IndexingPolicy::decide`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldMatch {
+    /// An entry with the same name and value exists.
+    Full,
+    /// An entry with the same name exists, but with a different value.
+    Name,
+    /// No entry has this name.
+    None,
+}
+
+/// How the encoder should represent a header field (RFC 7541 Section 6).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexingDecision {
+    /// Use the table index if the field is fully indexed; otherwise send a
+    /// literal and add it to the dynamic table (incremental indexing).
+    Index,
+    /// Use the table index if the field is fully indexed; otherwise send a
+    /// literal without touching the dynamic table.
+    NoIndex,
+    /// Always send a literal marked never-indexed, so intermediaries also
+    /// keep it out of their tables. Intended for sensitive values.
+    NeverIndex,
+}
+
+/// Decides the representation of each header field passed to
+/// [`HpackEncoder::encode`](super::HpackEncoder::encode).
+///
+/// Implemented for closures, so a policy can be written inline:
+///
+/// ```rust
+/// use h2_sans_io::{HpackEncoder, IndexingDecision};
+///
+/// let mut encoder = HpackEncoder::new();
+/// encoder.set_indexing_policy(|h: &h2_sans_io::H2Header, _| {
+///     if h.name == b":path" { IndexingDecision::NoIndex } else { IndexingDecision::Index }
+/// });
+/// ```
+pub trait IndexingPolicy: Send {
+    fn decide(&mut self, header: &H2Header, table_match: FieldMatch) -> IndexingDecision;
+}
+
+impl<F> IndexingPolicy for F
+where
+    F: FnMut(&H2Header, FieldMatch) -> IndexingDecision + Send,
+{
+    fn decide(&mut self, header: &H2Header, table_match: FieldMatch) -> IndexingDecision {
+        self(header, table_match)
+    }
+}
+
+/// The encoder's built-in policy: index a field only when its name is not in
+/// either table yet, so the dynamic table never holds two values for one name.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultIndexingPolicy;
+
+impl IndexingPolicy for DefaultIndexingPolicy {
+    fn decide(&mut self, _header: &H2Header, table_match: FieldMatch) -> IndexingDecision {
+        match table_match {
+            FieldMatch::None => IndexingDecision::Index,
+            FieldMatch::Full | FieldMatch::Name => IndexingDecision::NoIndex,
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! HPACK indexing tables (RFC 7541 Section 2.3).
+//!
+//! The static table and the dynamic table share a single 1-based index
+//! address space: indices 1..=61 refer to the static table, 62 onwards to
+//! the dynamic table (newest entry first).
+
+use std::collections::VecDeque;
+
+use crate::snapshot::{SnapshotError, SnapshotReader, SnapshotWriter};
+
+/// Per-entry overhead added to `name.len() + value.len()` when computing
+/// the size of a dynamic table entry (RFC 7541 Section 4.1).
+pub(crate) const ENTRY_OVERHEAD: usize = 32;
+
+/// Default dynamic table size (SETTINGS_HEADER_TABLE_SIZE initial value).
+pub(crate) const DEFAULT_TABLE_SIZE: usize = 4096;
+
+/// Size of an entry as defined by RFC 7541 Section 4.1.
+pub(crate) fn entry_size(name: &[u8], value: &[u8]) -> usize {
+    name.len() + value.len() + ENTRY_OVERHEAD
+}
+
+/// A read-only view of one dynamic table entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableEntry<'a> {
+    /// HPACK index the peer uses to reference this entry (62 or higher).
+    pub index: usize,
+    pub name: &'a [u8],
+    pub value: &'a [u8],
+    /// Entry size per RFC 7541 Section 4.1: name + value + 32 octets.
+    pub size: usize,
+}
+
+/// Dynamic table with FIFO eviction.
+#[derive(Debug, Clone)]
+pub(crate) struct DynamicTable {
+    entries: VecDeque<(Vec<u8>, Vec<u8>)>,
+    size: usize,
+    max_size: usize,
+}
+
+impl Default for DynamicTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_TABLE_SIZE)
+    }
+}
+
+impl DynamicTable {
+    pub(crate) fn new(max_size: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            size: 0,
+            max_size,
+        }
+    }
+
+    /// Current size in octets (sum of entry sizes).
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Maximum size in octets.
+    pub(crate) fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Entries in index order, starting at 62 (the newest entry).
+    pub(crate) fn entries(&self) -> Vec<TableEntry<'_>> {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, (name, value))| TableEntry {
+                index: STATIC_TABLE.len() + 1 + i,
+                name,
+                value,
+                size: entry_size(name, value),
+            })
+            .collect()
+    }
+
+    /// Entries from newest (index 62) to oldest.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.entries.iter().map(|(n, v)| (n.as_slice(), v.as_slice()))
+    }
+
+    /// Change the maximum size, evicting entries as needed.
+    pub(crate) fn set_max_size(&mut self, max_size: usize) {
+        self.max_size = max_size;
+        self.evict();
+    }
+
+    /// Insert a new entry. An entry larger than the maximum size empties the
+    /// table and is not inserted (RFC 7541 Section 4.4).
+    pub(crate) fn insert(&mut self, name: Vec<u8>, value: Vec<u8>) {
+        let size = entry_size(&name, &value);
+        if size > self.max_size {
+            self.entries.clear();
+            self.size = 0;
+            return;
+        }
+        self.size += size;
+        self.entries.push_front((name, value));
+        self.evict();
+    }
+
+    /// Append the maximum size and entries (newest first) to a snapshot.
+    pub(crate) fn write_snapshot(&self, w: &mut SnapshotWriter) {
+        w.u64(self.max_size as u64);
+        w.u32(self.entries.len() as u32);
+        for (name, value) in &self.entries {
+            w.bytes(name);
+            w.bytes(value);
+        }
+    }
+
+    /// Rebuild a table written by `write_snapshot`, preserving entry order.
+    pub(crate) fn read_snapshot(r: &mut SnapshotReader<'_>) -> Result<Self, SnapshotError> {
+        let mut table = Self::new(r.usize()?);
+        let count = r.u32()?;
+        for _ in 0..count {
+            let name = r.bytes()?.to_vec();
+            let value = r.bytes()?.to_vec();
+            table.size += entry_size(&name, &value);
+            table.entries.push_back((name, value));
+        }
+        if table.size > table.max_size {
+            return Err(SnapshotError::Invalid("dynamic table exceeds its maximum size"));
+        }
+        Ok(table)
+    }
+
+    fn evict(&mut self) {
+        while self.size > self.max_size {
+            match self.entries.pop_back() {
+                Some((name, value)) => self.size -= entry_size(&name, &value),
+                None => break,
+            }
+        }
+    }
+}
+
+/// Look up an entry by its 1-based HPACK index across both tables.
+pub(crate) fn get(dynamic: &DynamicTable, index: usize) -> Option<(&[u8], &[u8])> {
+    if index == 0 {
+        return None;
+    }
+    if index <= STATIC_TABLE.len() {
+        return Some(STATIC_TABLE[index - 1]);
+    }
+    dynamic
+        .entries
+        .get(index - STATIC_TABLE.len() - 1)
+        .map(|(n, v)| (n.as_slice(), v.as_slice()))
+}
+
+/// Result of searching the tables for a header field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TableMatch {
+    /// Name and value both match the entry at this index.
+    Full(usize),
+    /// Only the name matches the entry at this index.
+    Name(usize),
+    /// Neither table contains the name.
+    None,
+}
+
+/// Search the static table and (optionally) the dynamic table. A full match
+/// anywhere wins over a name-only match; among name-only matches the lowest
+/// index is preferred since it encodes in the fewest bytes.
+pub(crate) fn find(dynamic: Option<&DynamicTable>, name: &[u8], value: &[u8]) -> TableMatch {
+    let dynamic_entries = dynamic.into_iter().flat_map(|d| d.iter());
+    let mut name_match = None;
+    for (i, (n, v)) in STATIC_TABLE.iter().copied().chain(dynamic_entries).enumerate() {
+        if n == name {
+            if v == value {
+                return TableMatch::Full(i + 1);
+            }
+            name_match.get_or_insert(i + 1);
+        }
+    }
+    match name_match {
+        Some(index) => TableMatch::Name(index),
+        None => TableMatch::None,
+    }
+}
+
+/// The static table (RFC 7541 Appendix A).
+pub(crate) static STATIC_TABLE: [(&[u8], &[u8]); 61] = [
+    (b":authority", b""),
+    (b":method", b"GET"),
+    (b":method", b"POST"),
+    (b":path", b"/"),
+    (b":path", b"/index.html"),
+    (b":scheme", b"http"),
+    (b":scheme", b"https"),
+    (b":status", b"200"),
+    (b":status", b"204"),
+    (b":status", b"206"),
+    (b":status", b"304"),
+    (b":status", b"400"),
+    (b":status", b"404"),
+    (b":status", b"500"),
+    (b"accept-charset", b""),
+    (b"accept-encoding", b"gzip, deflate"),
+    (b"accept-language", b""),
+    (b"accept-ranges", b""),
+    (b"accept", b""),
+    (b"access-control-allow-origin", b""),
+    (b"age", b""),
+    (b"allow", b""),
+    (b"authorization", b""),
+    (b"cache-control", b""),
+    (b"content-disposition", b""),
+    (b"content-encoding", b""),
+    (b"content-language", b""),
+    (b"content-length", b""),
+    (b"content-location", b""),
+    (b"content-range", b""),
+    (b"content-type", b""),
+    (b"cookie", b""),
+    (b"date", b""),
+    (b"etag", b""),
+    (b"expect", b""),
+    (b"expires", b""),
+    (b"from", b""),
+    (b"host", b""),
+    (b"if-match", b""),
+    (b"if-modified-since", b""),
+    (b"if-none-match", b""),
+    (b"if-range", b""),
+    (b"if-unmodified-since", b""),
+    (b"last-modified", b""),
+    (b"link", b""),
+    (b"location", b""),
+    (b"max-forwards", b""),
+    (b"proxy-authenticate", b""),
+    (b"proxy-authorization", b""),
+    (b"range", b""),
+    (b"referer", b""),
+    (b"refresh", b""),
+    (b"retry-after", b""),
+    (b"server", b""),
+    (b"set-cookie", b""),
+    (b"strict-transport-security", b""),
+    (b"transfer-encoding", b""),
+    (b"user-agent", b""),
+    (b"vary", b""),
+    (b"via", b""),
+    (b"www-authenticate", b""),
+];
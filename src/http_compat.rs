@@ -0,0 +1,173 @@
+//! Conversions to and from `http` crate types (feature `http`)
+//!
+//! Decoded header lists become `http::request::Parts` and
+//! `http::response::Parts`, with pseudo-header fields mapped to the method,
+//! URI and status, and `:protocol` stored as a [`Protocol`] extension.
+//! The reverse direction produces header lists in wire order for
+//! `HpackEncoder::encode`, leaving out connection-specific fields and moving
+//! `host` into `:authority`.
+//!
+//! Pseudo-header rules are checked with [`H2Request::from_headers`] and
+//! [`H2Response::from_headers`]; field names and values must also be valid
+//! for the `http` crate.
+
+use http::header::{HeaderName, HeaderValue, HOST, TE};
+use http::uri::Scheme;
+use http::{Method, StatusCode, Uri, Version};
+
+use crate::hpack::H2Header;
+use crate::message::{H2Request, H2Response};
+use crate::validation::{HeaderError, CONNECTION_SPECIFIC};
+
+/// The `:protocol` pseudo-header of an extended CONNECT request (RFC 8441),
+/// stored in `http::Extensions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Protocol(pub String);
+
+/// A header list that cannot be represented with `http` crate types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpConversionError {
+    /// Pseudo-header fields are missing, duplicated or misplaced.
+    Malformed(HeaderError),
+    /// `:method` is not a valid method token.
+    InvalidMethod,
+    /// `:scheme`, `:authority` and `:path` do not form a valid URI.
+    InvalidUri,
+    /// A regular field name or value is rejected by the `http` crate.
+    /// Holds the position of the field among the regular fields.
+    InvalidHeader(usize),
+}
+
+impl std::fmt::Display for HttpConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpConversionError::Malformed(e) => write!(f, "{}", e),
+            HttpConversionError::InvalidMethod => write!(f, "invalid :method"),
+            HttpConversionError::InvalidUri => write!(f, "invalid request URI"),
+            HttpConversionError::InvalidHeader(index) => write!(f, "invalid header field {}", index),
+        }
+    }
+}
+
+impl std::error::Error for HttpConversionError {}
+
+impl From<HeaderError> for HttpConversionError {
+    fn from(e: HeaderError) -> Self {
+        HttpConversionError::Malformed(e)
+    }
+}
+
+/// Build request parts from a decoded header list.
+///
+/// The URI is absolute (`scheme://authority/path`) when `:authority` is
+/// present, origin-form otherwise, and authority-form for CONNECT. An
+/// origin-form URI cannot hold the scheme, so `:scheme` is then stored as a
+/// [`Scheme`] extension. The version is always `HTTP/2.0`.
+pub fn request_parts_from_headers(headers: Vec<H2Header>) -> Result<http::request::Parts, HttpConversionError> {
+    let request = H2Request::from_headers(headers)?;
+    let method = Method::from_bytes(request.method.as_bytes()).map_err(|_| HttpConversionError::InvalidMethod)?;
+    let scheme = match &request.scheme {
+        Some(scheme) => Some(scheme.parse::<Scheme>().map_err(|_| HttpConversionError::InvalidUri)?),
+        None => None,
+    };
+
+    let mut uri = Uri::builder();
+    if let (Some(scheme), Some(_)) = (&scheme, &request.authority) {
+        uri = uri.scheme(scheme.clone());
+    }
+    if let Some(authority) = &request.authority {
+        uri = uri.authority(authority.as_str());
+    }
+    if let Some(path) = &request.path {
+        uri = uri.path_and_query(path.as_str());
+    }
+    let uri = uri.build().map_err(|_| HttpConversionError::InvalidUri)?;
+
+    let mut builder = http::Request::builder().method(method).uri(uri).version(Version::HTTP_2);
+    if let (Some(scheme), None) = (scheme, &request.authority) {
+        builder = builder.extension(scheme);
+    }
+    if let Some(protocol) = request.protocol {
+        builder = builder.extension(Protocol(protocol));
+    }
+    let headers_mut = builder.headers_mut().expect("builder has no errors yet");
+    fill_header_map(headers_mut, request.headers)?;
+    let (parts, ()) = builder.body(()).expect("all parts validated").into_parts();
+    Ok(parts)
+}
+
+/// Build a header list from request parts: pseudo-headers from the method,
+/// URI and [`Protocol`] extension, then the regular fields.
+///
+/// `:scheme` comes from the URI or a [`Scheme`] extension and defaults to
+/// `https`, and `:path` defaults to `/` (or `*` for OPTIONS), except for
+/// plain CONNECT requests, which carry neither. A URI without authority takes
+/// `:authority` from the `host` field; `host` is otherwise dropped, as are
+/// connection-specific fields and a `te` other than `trailers`
+/// (RFC 9113 Section 8.2.2).
+pub fn request_parts_to_headers(parts: &http::request::Parts) -> Vec<H2Header> {
+    let protocol = parts.extensions.get::<Protocol>().map(|p| p.0.clone());
+    let plain_connect = parts.method == Method::CONNECT && protocol.is_none();
+    let (scheme, path) = if plain_connect {
+        (None, None)
+    } else {
+        let default_path = if parts.method == Method::OPTIONS { "*" } else { "/" };
+        let path = parts.uri.path_and_query().map_or(default_path, |p| p.as_str());
+        let path = if path.is_empty() { default_path } else { path };
+        let scheme = parts.uri.scheme().or_else(|| parts.extensions.get::<Scheme>());
+        (Some(scheme.map_or("https", |s| s.as_str()).to_string()), Some(path.to_string()))
+    };
+    let host = parts.headers.get(HOST).and_then(|v| v.to_str().ok()).filter(|v| !v.is_empty());
+    let request = H2Request {
+        method: parts.method.as_str().to_string(),
+        scheme,
+        authority: parts.uri.authority().map(|a| a.as_str()).or(host).map(str::to_string),
+        path,
+        protocol,
+        headers: header_list(&parts.headers).into_iter().filter(|h| h.name != HOST.as_str().as_bytes()).collect(),
+    };
+    request.into_headers()
+}
+
+/// Build response parts from a decoded header list.
+pub fn response_parts_from_headers(headers: Vec<H2Header>) -> Result<http::response::Parts, HttpConversionError> {
+    let response = H2Response::from_headers(headers)?;
+    // from_headers accepts exactly the three-digit codes StatusCode does
+    let status = StatusCode::from_u16(response.status).expect("validated :status");
+    let mut builder = http::Response::builder().status(status).version(Version::HTTP_2);
+    let headers_mut = builder.headers_mut().expect("builder has no errors yet");
+    fill_header_map(headers_mut, response.headers)?;
+    let (parts, ()) = builder.body(()).expect("all parts validated").into_parts();
+    Ok(parts)
+}
+
+/// Build a header list from response parts: `:status`, then the regular
+/// fields without connection-specific ones.
+pub fn response_parts_to_headers(parts: &http::response::Parts) -> Vec<H2Header> {
+    H2Response {
+        status: parts.status.as_u16(),
+        headers: header_list(&parts.headers),
+    }
+    .into_headers()
+}
+
+fn fill_header_map(map: &mut http::HeaderMap, headers: Vec<H2Header>) -> Result<(), HttpConversionError> {
+    for (index, h) in headers.into_iter().enumerate() {
+        let name = HeaderName::from_bytes(&h.name).map_err(|_| HttpConversionError::InvalidHeader(index))?;
+        let value = HeaderValue::from_bytes(&h.value).map_err(|_| HttpConversionError::InvalidHeader(index))?;
+        map.append(name, value);
+    }
+    Ok(())
+}
+
+/// Regular fields that may be sent in HTTP/2: connection-specific fields and
+/// `te` other than `trailers` are left out.
+fn header_list(map: &http::HeaderMap) -> Vec<H2Header> {
+    map.iter()
+        .filter(|(name, value)| {
+            !CONNECTION_SPECIFIC.contains(&name.as_str().as_bytes())
+                && (*name != TE || value.as_bytes().eq_ignore_ascii_case(b"trailers"))
+        })
+        .map(|(name, value)| H2Header::new(name.as_str(), value.as_bytes()))
+        .collect()
+}
@@ -8,10 +8,20 @@
 //! - **Sans-I/O Design**: No async runtime dependencies (no tokio)
 //! - **Pure Rust**: No C bindings, compiles to WASM
 //! - **RFC 7540 Compliant**: Handles DATA, HEADERS, CONTINUATION, SETTINGS,
-//!   RST_STREAM, GOAWAY, PING, WINDOW_UPDATE frames
-//! - **HPACK Support**: Header compression via fluke-hpack
+//!   RST_STREAM, GOAWAY, PING, WINDOW_UPDATE, PRIORITY frames
+//! - **HPACK Support**: Header compression with configurable Huffman coding
 //! - **Flow Control**: WINDOW_UPDATE handling and generation
 //! - **CONTINUATION Assembly**: Automatic header block reassembly
+//! - **Header Validation**: RFC 9113 field checks with strict and lenient profiles
+//! - **Stream Tracking**: Requests, interim responses, final responses and
+//!   trailers told apart per stream
+//! - **Priority Scheduling**: RFC 7540 dependency tree with weighted-fair
+//!   stream selection, and RFC 9218 urgency/incremental scheduling
+//! - **Alternative Services**: ALTSVC frames and RFC 7838 Alt-Svc field values
+//! - **Extension Frames**: Register handlers for custom frame types and
+//!   receive their typed events
+//! - **`http` Interop** (feature `http`): Conversions to and from `http` crate
+//!   request and response parts
 //!
 //! # Quick Start
 //!
@@ -27,7 +37,7 @@
 //!
 //! for event in events {
 //!     match event {
-//!         H2Event::Headers { stream_id, header_block, end_stream } => {
+//!         H2Event::Headers { stream_id, header_block, end_stream, .. } => {
 //!             println!("Headers on stream {}: {:?} bytes", stream_id, header_block.len());
 //!         }
 //!         H2Event::Data { stream_id, data, end_stream } => {
@@ -56,15 +66,55 @@
 //! - **Embedded HTTP/2 servers**: Resource-constrained environments
 //! - **Testing utilities**: Protocol testing without async complexity
 
+pub mod alt_svc;
+pub mod extensible_priority;
+pub mod extension;
 pub mod h2_codec;
+pub mod header_map;
+#[cfg(feature = "http")]
+pub mod http_compat;
 pub mod hpack;
+pub mod message;
+pub mod priority;
+mod snapshot;
+pub mod stream;
+pub mod validation;
 
+pub use alt_svc::{AltService, AltSvc, DEFAULT_MAX_AGE};
+pub use extension::{ExtensionEvent, ExtensionHandler};
+pub use extensible_priority::{
+    ExtensiblePriority, UrgencyScheduler, DEFAULT_MAX_UNOPENED_STREAMS, DEFAULT_URGENCY, MAX_URGENCY,
+};
 pub use h2_codec::{
-    H2Codec, H2Event, H2FrameHeader, StreamState,
+    H2Codec, H2Event, H2FrameHeader, PushError, StreamPriority, StreamState,
     CONNECTION_PREFACE, MAX_HEADER_BLOCK_SIZE, MAX_BUFFER_SIZE,
     MAX_FRAME_PAYLOAD_LENGTH,
     error_code, flags, frame_type, settings_id,
     is_h2c_preface,
 };
 
-pub use hpack::{H2Header, HpackDecoder, HpackEncoder};
+pub use header_map::HeaderMap;
+
+#[cfg(feature = "http")]
+pub use http_compat::{
+    request_parts_from_headers, request_parts_to_headers, response_parts_from_headers,
+    response_parts_to_headers, HttpConversionError, Protocol,
+};
+
+pub use hpack::{
+    encode_stateless, join_cookies, split_cookies, DecodedField, DefaultIndexingPolicy, FieldMatch,
+    H2Header, HpackDecoder, HpackEncoder, HpackError, HpackErrorKind, HuffmanStrategy,
+    IndexingDecision, IndexingPolicy, TableEntry,
+};
+
+pub use message::{H2Request, H2Response};
+pub use priority::{PriorityTree, DEFAULT_MAX_CLOSED_STREAMS, DEFAULT_MAX_IDLE_STREAMS, DEFAULT_WEIGHT};
+
+pub use snapshot::SnapshotError;
+
+pub use stream::{
+    ContinueState, Role, StreamError, StreamErrorKind, StreamEvent, StreamTracker,
+    DEFAULT_CONTINUE_TIMEOUT_MS,
+};
+
+pub use validation::{validate_headers, HeaderError, HeaderErrorKind, ValidationProfile};
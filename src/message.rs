@@ -0,0 +1,228 @@
+//! Typed request and response heads (RFC 9113 Section 8.3)
+//!
+//! [`H2Request`] and [`H2Response`] split a decoded header list into its
+//! pseudo-header fields and regular fields, checking that pseudo-headers come
+//! first, appear once and are valid for the direction and request form.
+//! Both convert back into an ordered header list for `HpackEncoder::encode`.
+//!
+//! Field names and values are not checked here; run
+//! [`crate::validate_headers`] on the list as well.
+
+use crate::hpack::H2Header;
+use crate::validation::{HeaderError, HeaderErrorKind};
+
+/// A request head: pseudo-header fields plus regular header fields.
+///
+/// Three forms are accepted (RFC 9113 Section 8.3.1 and 8.5, RFC 8441):
+/// - regular requests carry `:method`, `:scheme` and a non-empty `:path`,
+///   with an optional `:authority`;
+/// - CONNECT requests carry `:method` and `:authority` only;
+/// - extended CONNECT requests carry `:protocol` and all four of the others.
+#[derive(Debug, Clone, PartialEq)]
+pub struct H2Request {
+    pub method: String,
+    pub scheme: Option<String>,
+    pub authority: Option<String>,
+    pub path: Option<String>,
+    /// The `:protocol` of an extended CONNECT request (e.g. `websocket`).
+    pub protocol: Option<String>,
+    /// Regular header fields, in received order.
+    pub headers: Vec<H2Header>,
+}
+
+/// A response head: the `:status` code plus regular header fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct H2Response {
+    pub status: u16,
+    /// Regular header fields, in received order.
+    pub headers: Vec<H2Header>,
+}
+
+/// Pseudo-header names in the order they are written by `into_headers`.
+const REQUEST_PSEUDO: [&str; 5] = [":method", ":scheme", ":authority", ":path", ":protocol"];
+
+impl H2Request {
+    /// A regular request with the given method, scheme, authority and path.
+    pub fn new(method: impl Into<String>, scheme: impl Into<String>, authority: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            method: method.into(),
+            scheme: Some(scheme.into()),
+            authority: Some(authority.into()),
+            path: Some(path.into()),
+            protocol: None,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Split a decoded header list into a request head.
+    pub fn from_headers(headers: Vec<H2Header>) -> Result<Self, HeaderError> {
+        let total = headers.len();
+        let mut pseudo: [Option<(usize, String)>; 5] = Default::default();
+        let mut regular = Vec::with_capacity(total);
+
+        for (index, header) in headers.into_iter().enumerate() {
+            let err = |kind| HeaderError { kind, index };
+            if !header.name.starts_with(b":") {
+                regular.push(header);
+                continue;
+            }
+            if !regular.is_empty() {
+                return Err(err(HeaderErrorKind::PseudoAfterRegular));
+            }
+            let slot = REQUEST_PSEUDO
+                .iter()
+                .position(|name| name.as_bytes() == header.name)
+                .ok_or(err(HeaderErrorKind::UnexpectedPseudo))?;
+            if pseudo[slot].is_some() {
+                return Err(err(HeaderErrorKind::DuplicatePseudo));
+            }
+            let value = String::from_utf8(header.value).map_err(|_| err(HeaderErrorKind::InvalidPseudoValue))?;
+            pseudo[slot] = Some((index, value));
+        }
+
+        let [method, scheme, authority, path, protocol] = pseudo;
+        let missing = |name| HeaderError { kind: HeaderErrorKind::MissingPseudo(name), index: total };
+        let unexpected = |index| HeaderError { kind: HeaderErrorKind::UnexpectedPseudo, index };
+        let invalid = |index| HeaderError { kind: HeaderErrorKind::InvalidPseudoValue, index };
+
+        let (method_index, method) = method.ok_or_else(|| missing(":method"))?;
+        if method.is_empty() {
+            return Err(invalid(method_index));
+        }
+        let is_connect = method == "CONNECT";
+
+        if is_connect && protocol.is_none() {
+            // RFC 9113 Section 8.5: :scheme and :path MUST be omitted.
+            if let Some((index, _)) = scheme.as_ref().or(path.as_ref()) {
+                return Err(unexpected(*index));
+            }
+            authority.as_ref().ok_or_else(|| missing(":authority"))?;
+        } else {
+            // RFC 8441 Section 4: :protocol is only valid on CONNECT.
+            if let Some((index, _)) = protocol.as_ref().filter(|_| !is_connect) {
+                return Err(unexpected(*index));
+            }
+            scheme.as_ref().ok_or_else(|| missing(":scheme"))?;
+            let (path_index, path) = path.as_ref().ok_or_else(|| missing(":path"))?;
+            if path.is_empty() {
+                return Err(invalid(*path_index));
+            }
+            if protocol.is_some() {
+                authority.as_ref().ok_or_else(|| missing(":authority"))?;
+            }
+        }
+
+        Ok(Self {
+            method,
+            scheme: scheme.map(|(_, v)| v),
+            authority: authority.map(|(_, v)| v),
+            path: path.map(|(_, v)| v),
+            protocol: protocol.map(|(_, v)| v),
+            headers: regular,
+        })
+    }
+
+    /// True for a CONNECT request, plain or extended.
+    pub fn is_connect(&self) -> bool {
+        self.method == "CONNECT"
+    }
+
+    /// True for an extended CONNECT request (RFC 8441), i.e. one with `:protocol`.
+    pub fn is_extended_connect(&self) -> bool {
+        self.is_connect() && self.protocol.is_some()
+    }
+
+    /// Look up the first regular header with the given (lowercase) name.
+    pub fn header(&self, name: &str) -> Option<&H2Header> {
+        self.headers.iter().find(|h| h.name == name.as_bytes())
+    }
+
+    /// True if the request carries `expect: 100-continue` (RFC 9110 Section 10.1.1).
+    pub fn expects_continue(&self) -> bool {
+        self.header("expect").is_some_and(|h| h.value.eq_ignore_ascii_case(b"100-continue"))
+    }
+
+    /// Build the header list: pseudo-headers first, then regular headers.
+    pub fn into_headers(self) -> Vec<H2Header> {
+        let pseudo = [Some(self.method), self.scheme, self.authority, self.path, self.protocol];
+        let mut out = Vec::with_capacity(pseudo.len() + self.headers.len());
+        for (name, value) in REQUEST_PSEUDO.iter().zip(pseudo) {
+            if let Some(value) = value {
+                out.push(H2Header::new(*name, value));
+            }
+        }
+        out.extend(self.headers);
+        out
+    }
+}
+
+impl H2Response {
+    /// A response with the given status and no regular headers.
+    ///
+    /// # Panics
+    /// Panics if `status` is not a three-digit code (100..=999), or is 101,
+    /// which HTTP/2 does not allow (RFC 9113 Section 8.6).
+    pub fn new(status: u16) -> Self {
+        assert!((100..=999).contains(&status), "status must be three digits, got {}", status);
+        assert_ne!(status, 101, "101 (Switching Protocols) is not allowed in HTTP/2");
+        Self { status, headers: Vec::new() }
+    }
+
+    /// Split a decoded header list into a response head. A `:status` of 101
+    /// is rejected as [`HeaderErrorKind::InvalidPseudoValue`].
+    pub fn from_headers(headers: Vec<H2Header>) -> Result<Self, HeaderError> {
+        let total = headers.len();
+        let mut status = None;
+        let mut regular = Vec::with_capacity(total);
+
+        for (index, header) in headers.into_iter().enumerate() {
+            let err = |kind| HeaderError { kind, index };
+            if !header.name.starts_with(b":") {
+                regular.push(header);
+                continue;
+            }
+            if !regular.is_empty() {
+                return Err(err(HeaderErrorKind::PseudoAfterRegular));
+            }
+            if header.name != b":status" {
+                return Err(err(HeaderErrorKind::UnexpectedPseudo));
+            }
+            if status.is_some() {
+                return Err(err(HeaderErrorKind::DuplicatePseudo));
+            }
+            // RFC 9113 Section 8.6: HTTP/2 has no 101 (Switching Protocols).
+            let code = parse_status(&header.value).filter(|&code| code != 101);
+            status = Some(code.ok_or(err(HeaderErrorKind::InvalidPseudoValue))?);
+        }
+
+        let status = status.ok_or(HeaderError { kind: HeaderErrorKind::MissingPseudo(":status"), index: total })?;
+        Ok(Self { status, headers: regular })
+    }
+
+    /// True for a 1xx (interim) response. Parsed responses are never 101,
+    /// which HTTP/2 does not allow.
+    pub fn is_informational(&self) -> bool {
+        (100..200).contains(&self.status)
+    }
+
+    /// Look up the first regular header with the given (lowercase) name.
+    pub fn header(&self, name: &str) -> Option<&H2Header> {
+        self.headers.iter().find(|h| h.name == name.as_bytes())
+    }
+
+    /// Build the header list: `:status` first, then regular headers.
+    pub fn into_headers(self) -> Vec<H2Header> {
+        let mut out = Vec::with_capacity(1 + self.headers.len());
+        out.push(H2Header::new(":status", self.status.to_string()));
+        out.extend(self.headers);
+        out
+    }
+}
+
+/// Parse a `:status` value: exactly three ASCII digits, 100 or above.
+fn parse_status(value: &[u8]) -> Option<u16> {
+    if value.len() != 3 || !value.iter().all(u8::is_ascii_digit) || value[0] == b'0' {
+        return None;
+    }
+    Some(value.iter().fold(0, |acc, d| acc * 10 + u16::from(d - b'0')))
+}
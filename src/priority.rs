@@ -0,0 +1,386 @@
+//! RFC 7540 stream priority tree and weighted scheduler
+//!
+//! `H2Event::Priority` and the `priority` field of `H2Event::Headers` report
+//! what a client asked for; a [`PriorityTree`] keeps the resulting dependency
+//! graph (RFC 7540 Section 5.3) and decides which stream's queued DATA to send
+//! next.
+//!
+//! The tree does not see frames or flow-control windows. Tell it which streams
+//! have data ready to send with [`PriorityTree::set_ready`], then call
+//! [`PriorityTree::next_stream`] before writing each DATA frame. A stream is
+//! served before its dependents; siblings share in proportion to their
+//! weights.
+//!
+//! Closed streams stay in the tree for a while so that streams depending on
+//! them keep their place (RFC 7540 Section 5.3.4); the oldest closed stream is
+//! removed once more than [`PriorityTree::max_closed_streams`] are retained.
+//! Idle streams, added by PRIORITY frames before any HEADERS, are capped the
+//! same way by [`PriorityTree::max_idle_streams`], so a peer cannot grow the
+//! tree without bound.
+//!
+//! ```rust
+//! use h2_sans_io::{PriorityTree, StreamPriority};
+//!
+//! let mut tree = PriorityTree::new();
+//! tree.open(1, Some(StreamPriority { exclusive: false, dependency: 0, weight: 255 }));
+//! tree.open(3, Some(StreamPriority { exclusive: false, dependency: 0, weight: 0 }));
+//! tree.set_ready(1, true);
+//! tree.set_ready(3, true);
+//! assert_eq!(tree.next_stream(), Some(1));
+//! ```
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use crate::h2_codec::StreamPriority;
+
+/// Weight given to streams without explicit priority (RFC 7540 Section 5.3.5).
+pub const DEFAULT_WEIGHT: u16 = 16;
+
+/// Closed streams kept in the tree by default.
+pub const DEFAULT_MAX_CLOSED_STREAMS: usize = 16;
+
+/// Idle streams (known only from PRIORITY frames) kept in the tree by default.
+pub const DEFAULT_MAX_IDLE_STREAMS: usize = 16;
+
+/// Virtual time a stream of weight 1 advances per pick; a stream of weight
+/// `w` advances `STRIDE / w`.
+const STRIDE: u64 = 1 << 16;
+
+/// A node in the tree. Stream 0 is the root.
+#[derive(Debug, Clone)]
+struct Node {
+    parent: u32,
+    /// Effective weight, 1-256.
+    weight: u16,
+    children: Vec<u32>,
+    closed: bool,
+    ready: bool,
+    /// Ready streams below this node, so picks skip idle branches without
+    /// walking them.
+    ready_below: usize,
+    /// Virtual time at which this node is next due among its siblings.
+    pass: u64,
+    /// Virtual time of the last child picked under this node.
+    vtime: u64,
+}
+
+impl Node {
+    fn new(parent: u32, weight: u16) -> Self {
+        Self { parent, weight, children: Vec::new(), closed: false, ready: false, ready_below: 0, pass: 0, vtime: 0 }
+    }
+
+    /// Ready streams in this node's subtree, itself included.
+    fn ready_in_subtree(&self) -> usize {
+        usize::from(self.ready) + self.ready_below
+    }
+}
+
+/// Stream dependency tree with weighted-fair scheduling.
+#[derive(Debug, Clone)]
+pub struct PriorityTree {
+    nodes: HashMap<u32, Node>,
+    /// Closed streams still in the tree, oldest first.
+    closed: VecDeque<u32>,
+    max_closed: usize,
+    /// Streams added by PRIORITY frames and not opened yet, oldest first.
+    idle: VecDeque<u32>,
+    max_idle: usize,
+}
+
+impl Default for PriorityTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PriorityTree {
+    pub fn new() -> Self {
+        let mut nodes = HashMap::new();
+        nodes.insert(0, Node::new(0, DEFAULT_WEIGHT));
+        Self {
+            nodes,
+            closed: VecDeque::new(),
+            max_closed: DEFAULT_MAX_CLOSED_STREAMS,
+            idle: VecDeque::new(),
+            max_idle: DEFAULT_MAX_IDLE_STREAMS,
+        }
+    }
+
+    /// Set how many closed streams are retained (default
+    /// [`DEFAULT_MAX_CLOSED_STREAMS`]). Excess closed streams are removed,
+    /// oldest first.
+    pub fn set_max_closed_streams(&mut self, max: usize) {
+        self.max_closed = max;
+        self.evict_closed();
+    }
+
+    /// Number of closed streams retained.
+    pub fn max_closed_streams(&self) -> usize {
+        self.max_closed
+    }
+
+    /// Set how many idle streams, known only from PRIORITY frames, are
+    /// retained (default [`DEFAULT_MAX_IDLE_STREAMS`]). Excess idle streams
+    /// are removed, oldest first.
+    pub fn set_max_idle_streams(&mut self, max: usize) {
+        self.max_idle = max;
+        self.evict_idle();
+    }
+
+    /// Number of idle streams retained.
+    pub fn max_idle_streams(&self) -> usize {
+        self.max_idle
+    }
+
+    /// Add a stream opened by HEADERS, with the frame's priority fields if it
+    /// had any. A stream already in the tree (from an earlier PRIORITY frame)
+    /// keeps its place unless `priority` is given.
+    pub fn open(&mut self, stream_id: u32, priority: Option<StreamPriority>) {
+        if stream_id == 0 {
+            return;
+        }
+        self.insert_default(stream_id, false);
+        self.idle.retain(|&id| id != stream_id);
+        if let Some(priority) = priority {
+            self.reprioritize(stream_id, priority);
+        }
+    }
+
+    /// Apply a PRIORITY frame. Streams not yet in the tree (idle streams) are
+    /// added. A dependency on a stream not in the tree gives the stream the
+    /// default priority (RFC 7540 Section 5.3.1); a dependency on itself is
+    /// ignored, as the codec already reports it as a stream error.
+    pub fn reprioritize(&mut self, stream_id: u32, priority: StreamPriority) {
+        if stream_id == 0 || priority.dependency == stream_id {
+            return;
+        }
+        self.insert_default(stream_id, true);
+
+        let (dependency, weight, exclusive) = if self.nodes.contains_key(&priority.dependency) {
+            (priority.dependency, priority.effective_weight(), priority.exclusive)
+        } else {
+            (0, DEFAULT_WEIGHT, false)
+        };
+
+        // RFC 7540 Section 5.3.3: a stream made dependent on one of its own
+        // dependents first moves that dependent to its former parent.
+        if self.is_descendant(dependency, stream_id) {
+            let former_parent = self.nodes[&stream_id].parent;
+            self.attach(dependency, former_parent);
+        }
+
+        self.detach(stream_id);
+        self.node_mut(stream_id).weight = weight;
+        self.link(stream_id, dependency);
+        if exclusive {
+            let siblings: Vec<u32> = self.nodes[&dependency].children.iter().copied().filter(|&c| c != stream_id).collect();
+            for child in siblings {
+                self.attach(child, stream_id);
+            }
+        }
+        self.evict_idle();
+    }
+
+    /// Mark a stream closed. It is no longer scheduled but stays in the tree
+    /// until evicted, keeping its dependents in place.
+    pub fn close(&mut self, stream_id: u32) {
+        if stream_id == 0 || self.nodes.get(&stream_id).is_none_or(|n| n.closed) {
+            return;
+        }
+        self.set_ready(stream_id, false);
+        self.node_mut(stream_id).closed = true;
+        self.idle.retain(|&id| id != stream_id);
+        self.closed.push_back(stream_id);
+        self.evict_closed();
+    }
+
+    /// Remove a stream from the tree. Its dependents move to its parent and
+    /// share its weight in proportion to their own (RFC 7540 Section 5.3.4).
+    pub fn remove(&mut self, stream_id: u32) {
+        if stream_id == 0 || !self.nodes.contains_key(&stream_id) {
+            return;
+        }
+        let parent = self.nodes[&stream_id].parent;
+        let weight = u32::from(self.nodes[&stream_id].weight);
+        self.detach(stream_id);
+        let node = self.nodes.remove(&stream_id).expect("checked above");
+        let total: u32 = node.children.iter().map(|c| u32::from(self.nodes[c].weight)).sum();
+        for child in node.children {
+            let child_weight = u32::from(self.nodes[&child].weight);
+            let share = (weight * child_weight / total).clamp(1, 256) as u16;
+            self.node_mut(child).weight = share;
+            self.link(child, parent);
+        }
+        self.closed.retain(|&id| id != stream_id);
+        self.idle.retain(|&id| id != stream_id);
+    }
+
+    /// Record whether a stream has DATA queued and may send it.
+    pub fn set_ready(&mut self, stream_id: u32, ready: bool) {
+        let parent = match self.nodes.get_mut(&stream_id) {
+            Some(node) if stream_id != 0 && !node.closed && node.ready != ready => {
+                node.ready = ready;
+                node.parent
+            }
+            _ => return,
+        };
+        if ready {
+            self.add_ready_below(parent, 1);
+        } else {
+            self.remove_ready_below(parent, 1);
+        }
+    }
+
+    pub fn is_ready(&self, stream_id: u32) -> bool {
+        self.nodes.get(&stream_id).is_some_and(|n| stream_id != 0 && n.ready)
+    }
+
+    /// Pick the stream that should send the next DATA frame, or `None` if no
+    /// stream is ready. Call once per frame: each call advances the chosen
+    /// branch's share.
+    pub fn next_stream(&mut self) -> Option<u32> {
+        if self.nodes[&0].ready_below == 0 {
+            return None;
+        }
+        let mut current = 0;
+        loop {
+            if current != 0 && self.nodes[&current].ready {
+                return Some(current);
+            }
+            let vtime = self.nodes[&current].vtime;
+            let (child, pass) = self.nodes[&current]
+                .children
+                .iter()
+                .filter(|c| self.nodes[c].ready_in_subtree() > 0)
+                .map(|&c| (c, self.nodes[&c].pass.max(vtime)))
+                .min_by_key(|&(c, pass)| (pass, c))
+                .expect("active node has an active child");
+            let stride = STRIDE / u64::from(self.nodes[&child].weight);
+            self.node_mut(child).pass = pass + stride;
+            self.node_mut(current).vtime = pass;
+            current = child;
+        }
+    }
+
+    /// The priority of a stream in the tree: its parent and weight. The
+    /// exclusive flag only applies when priorities change and is always false.
+    pub fn priority(&self, stream_id: u32) -> Option<StreamPriority> {
+        let node = self.nodes.get(&stream_id).filter(|_| stream_id != 0)?;
+        Some(StreamPriority { exclusive: false, dependency: node.parent, weight: (node.weight - 1) as u8 })
+    }
+
+    /// Streams that depend directly on `stream_id` (0 for the root).
+    pub fn children(&self, stream_id: u32) -> &[u32] {
+        self.nodes.get(&stream_id).map_or(&[], |n| &n.children)
+    }
+
+    pub fn contains(&self, stream_id: u32) -> bool {
+        stream_id != 0 && self.nodes.contains_key(&stream_id)
+    }
+
+    pub fn is_closed(&self, stream_id: u32) -> bool {
+        self.nodes.get(&stream_id).is_some_and(|n| n.closed)
+    }
+
+    /// Number of streams in the tree, open or closed.
+    pub fn len(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn node_mut(&mut self, stream_id: u32) -> &mut Node {
+        self.nodes.get_mut(&stream_id).expect("stream in tree")
+    }
+
+    fn insert_default(&mut self, stream_id: u32, idle: bool) {
+        if let Entry::Vacant(entry) = self.nodes.entry(stream_id) {
+            entry.insert(Node::new(0, DEFAULT_WEIGHT));
+            self.node_mut(0).children.push(stream_id);
+            if idle {
+                self.idle.push_back(stream_id);
+            }
+        }
+    }
+
+    /// True if `stream_id` is below `ancestor` in the tree.
+    fn is_descendant(&self, mut stream_id: u32, ancestor: u32) -> bool {
+        // New streams have no dependents; skip the walk to the root.
+        if self.nodes[&ancestor].children.is_empty() {
+            return false;
+        }
+        while stream_id != 0 {
+            stream_id = self.nodes[&stream_id].parent;
+            if stream_id == ancestor {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Move a node (with its subtree) under a new parent.
+    fn attach(&mut self, stream_id: u32, parent: u32) {
+        self.detach(stream_id);
+        self.link(stream_id, parent);
+    }
+
+    fn detach(&mut self, stream_id: u32) {
+        let parent = self.nodes[&stream_id].parent;
+        self.node_mut(parent).children.retain(|&c| c != stream_id);
+        let ready = self.nodes[&stream_id].ready_in_subtree();
+        self.remove_ready_below(parent, ready);
+    }
+
+    fn link(&mut self, stream_id: u32, parent: u32) {
+        self.node_mut(stream_id).parent = parent;
+        self.node_mut(parent).children.push(stream_id);
+        let ready = self.nodes[&stream_id].ready_in_subtree();
+        self.add_ready_below(parent, ready);
+    }
+
+    /// Add `count` ready streams below `stream_id` and each of its ancestors.
+    fn add_ready_below(&mut self, mut stream_id: u32, count: usize) {
+        if count == 0 {
+            return;
+        }
+        loop {
+            let node = self.node_mut(stream_id);
+            node.ready_below += count;
+            if stream_id == 0 {
+                return;
+            }
+            stream_id = node.parent;
+        }
+    }
+
+    fn remove_ready_below(&mut self, mut stream_id: u32, count: usize) {
+        if count == 0 {
+            return;
+        }
+        loop {
+            let node = self.node_mut(stream_id);
+            node.ready_below -= count;
+            if stream_id == 0 {
+                return;
+            }
+            stream_id = node.parent;
+        }
+    }
+
+    fn evict_closed(&mut self) {
+        while self.closed.len() > self.max_closed {
+            let oldest = self.closed.pop_front().expect("non-empty");
+            self.remove(oldest);
+        }
+    }
+
+    fn evict_idle(&mut self) {
+        while self.idle.len() > self.max_idle {
+            let oldest = self.idle.pop_front().expect("non-empty");
+            self.remove(oldest);
+        }
+    }
+}
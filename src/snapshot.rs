@@ -0,0 +1,140 @@
+//! Binary snapshot format shared by the HPACK and codec state exports.
+//!
+//! Every snapshot starts with a 4-byte magic identifying what it holds and a
+//! 1-byte format version, followed by big-endian fields. Byte strings are
+//! prefixed with a `u32` length. The format is only meant to move live state
+//! between instances of this crate; it is not a stable interchange format
+//! across versions, which is what the version byte guards against.
+
+/// Error restoring state from a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The snapshot ended before all fields were read.
+    Truncated,
+    /// The magic bytes do not match the type being restored.
+    WrongKind,
+    /// The snapshot was written by an incompatible format version.
+    UnsupportedVersion(u8),
+    /// A field held a value that cannot be restored.
+    Invalid(&'static str),
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Truncated => write!(f, "snapshot truncated"),
+            SnapshotError::WrongKind => write!(f, "snapshot is for a different type"),
+            SnapshotError::UnsupportedVersion(v) => write!(f, "unsupported snapshot version {}", v),
+            SnapshotError::Invalid(what) => write!(f, "invalid snapshot: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+pub(crate) struct SnapshotWriter {
+    buf: Vec<u8>,
+}
+
+impl SnapshotWriter {
+    pub(crate) fn new(magic: &[u8; 4], version: u8) -> Self {
+        let mut buf = magic.to_vec();
+        buf.push(version);
+        Self { buf }
+    }
+
+    pub(crate) fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    pub(crate) fn bool(&mut self, v: bool) {
+        self.buf.push(v as u8);
+    }
+
+    pub(crate) fn u32(&mut self, v: u32) {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    pub(crate) fn u64(&mut self, v: u64) {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    pub(crate) fn bytes(&mut self, v: &[u8]) {
+        self.u32(v.len() as u32);
+        self.buf.extend_from_slice(v);
+    }
+
+    pub(crate) fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+pub(crate) struct SnapshotReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> SnapshotReader<'a> {
+    /// Check the magic and version, positioning the reader at the first field.
+    pub(crate) fn new(buf: &'a [u8], magic: &[u8; 4], version: u8) -> Result<Self, SnapshotError> {
+        if buf.len() < 5 {
+            return Err(SnapshotError::Truncated);
+        }
+        if &buf[..4] != magic {
+            return Err(SnapshotError::WrongKind);
+        }
+        if buf[4] != version {
+            return Err(SnapshotError::UnsupportedVersion(buf[4]));
+        }
+        Ok(Self { buf, pos: 5 })
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], SnapshotError> {
+        let end = self.pos.checked_add(n).ok_or(SnapshotError::Truncated)?;
+        let out = self.buf.get(self.pos..end).ok_or(SnapshotError::Truncated)?;
+        self.pos = end;
+        Ok(out)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn bool(&mut self) -> Result<bool, SnapshotError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(SnapshotError::Invalid("boolean out of range")),
+        }
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, SnapshotError> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, SnapshotError> {
+        let b = self.take(8)?;
+        let mut arr = [0u8; 8];
+        arr.copy_from_slice(b);
+        Ok(u64::from_be_bytes(arr))
+    }
+
+    /// Read a `u64` that must fit in `usize` on this platform.
+    pub(crate) fn usize(&mut self) -> Result<usize, SnapshotError> {
+        usize::try_from(self.u64()?).map_err(|_| SnapshotError::Invalid("size exceeds platform usize"))
+    }
+
+    pub(crate) fn bytes(&mut self) -> Result<&'a [u8], SnapshotError> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    /// Fail if unread bytes remain.
+    pub(crate) fn finish(self) -> Result<(), SnapshotError> {
+        if self.pos != self.buf.len() {
+            return Err(SnapshotError::Invalid("trailing bytes"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,443 @@
+//! Per-stream message tracking on top of `H2Codec` events
+//!
+//! `H2Event::Headers` looks the same for every header block on a stream. A
+//! [`StreamTracker`] follows each stream through the HTTP message exchange
+//! (RFC 9113 Section 8.1) and classifies decoded header blocks as a request,
+//! an interim (1xx) response, a final response or trailers, rejecting blocks
+//! and DATA frames that arrive out of place.
+//!
+//! The tracker does not decode HPACK itself: every header block must go
+//! through the connection's single `HpackDecoder` in order, whatever the
+//! tracker later decides about the stream.
+//!
+//! It also checks the sum of DATA payload lengths against `content-length`
+//! (RFC 9113 Section 8.1.1): an overshoot is reported as soon as it happens,
+//! a shortfall at END_STREAM. Responses to HEAD and CONNECT requests and
+//! 204/304 responses carry no content of the declared length and are not
+//! checked; a client must register its requests with
+//! [`StreamTracker::send_request`] for HEAD and CONNECT to be recognized.
+//!
+//! On the client side it also implements `Expect: 100-continue`: register
+//! outgoing requests with [`StreamTracker::send_request`] and ask
+//! [`StreamTracker::continue_state`] before sending the body. Time is passed
+//! in by the caller as milliseconds from any fixed origin, so the tracker
+//! never reads a clock.
+//!
+//! ```rust
+//! use h2_sans_io::{H2Header, Role, StreamEvent, StreamTracker};
+//!
+//! let mut tracker = StreamTracker::new(Role::Server);
+//! let headers = vec![
+//!     H2Header::new(":method", "GET"),
+//!     H2Header::new(":scheme", "https"),
+//!     H2Header::new(":path", "/"),
+//! ];
+//! match tracker.recv_headers(1, headers, true) {
+//!     StreamEvent::Request { request, .. } => assert_eq!(request.method, "GET"),
+//!     other => panic!("unexpected {:?}", other),
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use crate::h2_codec::error_code;
+use crate::hpack::H2Header;
+use crate::message::{H2Request, H2Response};
+use crate::validation::{validate_headers, HeaderError, HeaderErrorKind, ValidationProfile};
+
+/// Which end of the connection the tracker runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Receives responses (and sends requests).
+    Client,
+    /// Receives requests (and sends responses).
+    Server,
+}
+
+/// A classified header block or DATA frame, returned by [`StreamTracker`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// Request head received by a server.
+    Request {
+        stream_id: u32,
+        request: H2Request,
+        end_stream: bool,
+    },
+    /// Interim (1xx) response received by a client. Any number may precede
+    /// the final response.
+    Informational {
+        stream_id: u32,
+        response: H2Response,
+    },
+    /// Final response head received by a client.
+    Response {
+        stream_id: u32,
+        response: H2Response,
+        end_stream: bool,
+    },
+    /// Trailer fields closing the message. Always ends the stream.
+    Trailers {
+        stream_id: u32,
+        headers: Vec<H2Header>,
+    },
+    /// Message body data.
+    Data {
+        stream_id: u32,
+        data: Vec<u8>,
+        end_stream: bool,
+    },
+    /// The stream must be reset with `error.error_code()`. The tracker treats
+    /// the stream as closed from here on.
+    StreamError {
+        stream_id: u32,
+        error: StreamError,
+    },
+}
+
+/// The reason a stream was rejected, reported by [`StreamError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamErrorKind {
+    /// The header block is malformed: invalid fields or pseudo-headers,
+    /// including pseudo-headers in trailers.
+    Malformed(HeaderError),
+    /// A trailer block without END_STREAM (RFC 9113 Section 8.1).
+    TrailersWithoutEndStream,
+    /// An interim (1xx) response carried END_STREAM.
+    InformationalEndStream,
+    /// DATA arrived before the message head.
+    DataBeforeHeaders,
+    /// A frame arrived after the stream ended.
+    StreamClosed,
+    /// The `content-length` field is not a decimal number, or appears with
+    /// differing values.
+    InvalidContentLength,
+    /// The DATA received does not add up to `content-length`: more arrived
+    /// than declared, or less by END_STREAM.
+    ContentLengthMismatch { declared: u64, received: u64 },
+}
+
+/// A stream-level error detected by [`StreamTracker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamError {
+    pub kind: StreamErrorKind,
+}
+
+impl StreamError {
+    /// HTTP/2 error code for RST_STREAM: STREAM_CLOSED for frames after the
+    /// end of the stream, PROTOCOL_ERROR otherwise.
+    pub fn error_code(&self) -> u32 {
+        match self.kind {
+            StreamErrorKind::StreamClosed => error_code::STREAM_CLOSED,
+            _ => error_code::PROTOCOL_ERROR,
+        }
+    }
+}
+
+impl std::fmt::Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            StreamErrorKind::Malformed(e) => write!(f, "{}", e),
+            StreamErrorKind::TrailersWithoutEndStream => write!(f, "trailers without END_STREAM"),
+            StreamErrorKind::InformationalEndStream => write!(f, "informational response with END_STREAM"),
+            StreamErrorKind::DataBeforeHeaders => write!(f, "DATA before message headers"),
+            StreamErrorKind::StreamClosed => write!(f, "frame received after end of stream"),
+            StreamErrorKind::InvalidContentLength => write!(f, "invalid content-length"),
+            StreamErrorKind::ContentLengthMismatch { declared, received } => {
+                write!(f, "content-length {} but {} bytes of DATA received", declared, received)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+/// Progress of an `Expect: 100-continue` request body, reported by
+/// [`StreamTracker::continue_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContinueState {
+    /// The request did not ask for 100-continue; send the body freely.
+    NotExpected,
+    /// Waiting for a 100 (Continue) response until `deadline_ms`.
+    Waiting { deadline_ms: u64 },
+    /// The server sent 100 (Continue); send the body.
+    Continue,
+    /// No 100 arrived before the deadline; send the body anyway
+    /// (RFC 9110 Section 10.1.1).
+    TimedOut,
+    /// The final response arrived first; the body should not be sent.
+    /// Reset the stream with NO_ERROR or CANCEL if it is still open.
+    FinalResponse,
+}
+
+impl ContinueState {
+    /// True if the request body may be sent now.
+    pub fn may_send_body(&self) -> bool {
+        matches!(self, ContinueState::NotExpected | ContinueState::Continue | ContinueState::TimedOut)
+    }
+}
+
+/// Default time a client waits for 100 (Continue) before sending the body.
+pub const DEFAULT_CONTINUE_TIMEOUT_MS: u64 = 1000;
+
+/// Where a stream is in its message exchange.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Waiting for the request or (final) response head.
+    #[default]
+    Head,
+    /// Head received; DATA or trailers may follow.
+    Body,
+    /// END_STREAM received, or the stream failed.
+    Closed,
+}
+
+/// Outgoing 100-continue expectation of a client stream.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    #[default]
+    None,
+    Waiting(u64),
+    Continue,
+    FinalResponse,
+}
+
+#[derive(Debug, Default)]
+struct TrackedStream {
+    phase: Phase,
+    expect: Expect,
+    /// Method of the request this client sent, if registered.
+    method: Option<String>,
+    /// Declared `content-length`, if the body length is checked.
+    content_length: Option<u64>,
+    /// Sum of DATA payload lengths received so far.
+    received: u64,
+}
+
+/// Classifies header blocks and DATA on each stream of one connection.
+///
+/// Feed it every decoded header block and DATA payload received; streams
+/// are created on first sight. Like `H2Codec`, it keeps per-stream state
+/// until [`StreamTracker::remove_stream`] is called.
+#[derive(Debug)]
+pub struct StreamTracker {
+    role: Role,
+    profile: ValidationProfile,
+    continue_timeout_ms: u64,
+    streams: HashMap<u32, TrackedStream>,
+}
+
+impl StreamTracker {
+    /// Create a tracker validating header fields with the strict profile.
+    pub fn new(role: Role) -> Self {
+        Self::with_validation(role, ValidationProfile::Strict)
+    }
+
+    /// Create a tracker validating header fields with `profile`.
+    pub fn with_validation(role: Role, profile: ValidationProfile) -> Self {
+        Self {
+            role,
+            profile,
+            continue_timeout_ms: DEFAULT_CONTINUE_TIMEOUT_MS,
+            streams: HashMap::new(),
+        }
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Set how long a client waits for 100 (Continue) before sending the body
+    /// anyway. Applies to requests registered afterwards.
+    pub fn set_continue_timeout(&mut self, timeout_ms: u64) {
+        self.continue_timeout_ms = timeout_ms;
+    }
+
+    /// Register a request sent by this client on `stream_id` at time `now_ms`.
+    /// The method is remembered so that responses to HEAD and CONNECT are
+    /// exempt from the content-length check.
+    ///
+    /// If the request carries `expect: 100-continue` and a body will follow
+    /// (`end_stream` is false), the body is held back until a 100 arrives or
+    /// the continue timeout passes; see [`StreamTracker::continue_state`].
+    ///
+    /// # Panics
+    /// Panics if the tracker runs on the server role.
+    pub fn send_request(&mut self, stream_id: u32, request: &H2Request, end_stream: bool, now_ms: u64) {
+        assert!(self.role == Role::Client, "send_request called on a server-side tracker");
+        let expect = if request.expects_continue() && !end_stream {
+            Expect::Waiting(now_ms.saturating_add(self.continue_timeout_ms))
+        } else {
+            Expect::None
+        };
+        let method = Some(request.method.clone());
+        self.streams.insert(stream_id, TrackedStream { expect, method, ..Default::default() });
+    }
+
+    /// Whether the request body on `stream_id` may be sent at time `now_ms`.
+    pub fn continue_state(&self, stream_id: u32, now_ms: u64) -> ContinueState {
+        match self.streams.get(&stream_id).map(|s| s.expect).unwrap_or_default() {
+            Expect::None => ContinueState::NotExpected,
+            Expect::Waiting(deadline_ms) if now_ms >= deadline_ms => ContinueState::TimedOut,
+            Expect::Waiting(deadline_ms) => ContinueState::Waiting { deadline_ms },
+            Expect::Continue => ContinueState::Continue,
+            Expect::FinalResponse => ContinueState::FinalResponse,
+        }
+    }
+
+    /// Earliest deadline of any stream waiting for 100 (Continue), for the
+    /// caller's timer. Streams past their deadline are included until a
+    /// response arrives or the stream is removed.
+    pub fn next_continue_deadline(&self) -> Option<u64> {
+        self.streams
+            .values()
+            .filter_map(|s| match s.expect {
+                Expect::Waiting(deadline_ms) => Some(deadline_ms),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Classify a decoded header block received on `stream_id`.
+    pub fn recv_headers(&mut self, stream_id: u32, headers: Vec<H2Header>, end_stream: bool) -> StreamEvent {
+        let phase = self.streams.get(&stream_id).map(|s| s.phase).unwrap_or_default();
+        let result = match phase {
+            Phase::Head => self.recv_head(stream_id, headers, end_stream),
+            Phase::Body => self.recv_trailers(stream_id, headers, end_stream),
+            Phase::Closed => Err(StreamErrorKind::StreamClosed),
+        };
+        self.finish(stream_id, result)
+    }
+
+    /// Check a DATA payload received on `stream_id`.
+    pub fn recv_data(&mut self, stream_id: u32, data: Vec<u8>, end_stream: bool) -> StreamEvent {
+        let result = match self.streams.get(&stream_id).map(|s| s.phase).unwrap_or_default() {
+            Phase::Head => Err(StreamErrorKind::DataBeforeHeaders),
+            Phase::Body => {
+                let stream = self.streams.entry(stream_id).or_default();
+                stream.received = stream.received.saturating_add(data.len() as u64);
+                check_content_length(stream, end_stream).map(|()| {
+                    if end_stream {
+                        stream.phase = Phase::Closed;
+                    }
+                    StreamEvent::Data { stream_id, data, end_stream }
+                })
+            }
+            Phase::Closed => Err(StreamErrorKind::StreamClosed),
+        };
+        self.finish(stream_id, result)
+    }
+
+    /// Forget a stream's state (e.g. once it is fully done or reset).
+    pub fn remove_stream(&mut self, stream_id: u32) {
+        self.streams.remove(&stream_id);
+    }
+
+    /// Number of streams currently tracked.
+    pub fn stream_count(&self) -> usize {
+        self.streams.len()
+    }
+
+    fn recv_head(&mut self, stream_id: u32, headers: Vec<H2Header>, end_stream: bool) -> Result<StreamEvent, StreamErrorKind> {
+        validate_headers(&headers, self.profile).map_err(StreamErrorKind::Malformed)?;
+        let next = if end_stream { Phase::Closed } else { Phase::Body };
+        match self.role {
+            Role::Server => {
+                let request = H2Request::from_headers(headers).map_err(StreamErrorKind::Malformed)?;
+                let content_length = parse_content_length(&request.headers)?;
+                let stream = self.streams.entry(stream_id).or_default();
+                stream.content_length = content_length.filter(|_| !request.is_connect());
+                check_content_length(stream, end_stream)?;
+                stream.phase = next;
+                Ok(StreamEvent::Request { stream_id, request, end_stream })
+            }
+            Role::Client => {
+                let response = H2Response::from_headers(headers).map_err(StreamErrorKind::Malformed)?;
+                let stream = self.streams.entry(stream_id).or_default();
+                if response.is_informational() {
+                    if end_stream {
+                        return Err(StreamErrorKind::InformationalEndStream);
+                    }
+                    if response.status == 100 && matches!(stream.expect, Expect::Waiting(_)) {
+                        stream.expect = Expect::Continue;
+                    }
+                    return Ok(StreamEvent::Informational { stream_id, response });
+                }
+                if matches!(stream.expect, Expect::Waiting(_)) {
+                    stream.expect = Expect::FinalResponse;
+                }
+                let content_length = parse_content_length(&response.headers)?;
+                let no_content = matches!(response.status, 204 | 304)
+                    || match stream.method.as_deref() {
+                        Some("HEAD") => true,
+                        Some("CONNECT") => (200..300).contains(&response.status),
+                        _ => false,
+                    };
+                stream.content_length = content_length.filter(|_| !no_content);
+                check_content_length(stream, end_stream)?;
+                stream.phase = next;
+                Ok(StreamEvent::Response { stream_id, response, end_stream })
+            }
+        }
+    }
+
+    fn recv_trailers(&mut self, stream_id: u32, headers: Vec<H2Header>, end_stream: bool) -> Result<StreamEvent, StreamErrorKind> {
+        if !end_stream {
+            return Err(StreamErrorKind::TrailersWithoutEndStream);
+        }
+        // RFC 9113 Section 8.1: trailers MUST NOT include pseudo-header fields.
+        if let Some(index) = headers.iter().position(|h| h.name.starts_with(b":")) {
+            return Err(StreamErrorKind::Malformed(HeaderError { kind: HeaderErrorKind::UnexpectedPseudo, index }));
+        }
+        validate_headers(&headers, self.profile).map_err(StreamErrorKind::Malformed)?;
+        let stream = self.streams.entry(stream_id).or_default();
+        check_content_length(stream, true)?;
+        stream.phase = Phase::Closed;
+        Ok(StreamEvent::Trailers { stream_id, headers })
+    }
+
+    fn set_phase(&mut self, stream_id: u32, phase: Phase) {
+        self.streams.entry(stream_id).or_default().phase = phase;
+    }
+
+    /// Turn a classification result into an event, closing the stream on error.
+    fn finish(&mut self, stream_id: u32, result: Result<StreamEvent, StreamErrorKind>) -> StreamEvent {
+        result.unwrap_or_else(|kind| {
+            self.set_phase(stream_id, Phase::Closed);
+            StreamEvent::StreamError { stream_id, error: StreamError { kind } }
+        })
+    }
+}
+
+/// Parse `content-length`, accepting repeated fields or list elements only
+/// when they agree (RFC 9110 Section 8.6).
+fn parse_content_length(headers: &[H2Header]) -> Result<Option<u64>, StreamErrorKind> {
+    let mut length = None;
+    for header in headers.iter().filter(|h| h.name == b"content-length") {
+        for part in header.value.split(|&b| b == b',') {
+            let digits = part.trim_ascii();
+            if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+                return Err(StreamErrorKind::InvalidContentLength);
+            }
+            let value = std::str::from_utf8(digits)
+                .ok()
+                .and_then(|d| d.parse::<u64>().ok())
+                .ok_or(StreamErrorKind::InvalidContentLength)?;
+            if length.is_some_and(|l| l != value) {
+                return Err(StreamErrorKind::InvalidContentLength);
+            }
+            length = Some(value);
+        }
+    }
+    Ok(length)
+}
+
+/// Compare received DATA with the declared length: more is an error at any
+/// point, less only once the stream ends.
+fn check_content_length(stream: &TrackedStream, end_stream: bool) -> Result<(), StreamErrorKind> {
+    match stream.content_length {
+        Some(declared) if stream.received > declared || (end_stream && stream.received != declared) => {
+            Err(StreamErrorKind::ContentLengthMismatch { declared, received: stream.received })
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,170 @@
+//! Header field validation (RFC 9113 Section 8.2)
+//!
+//! HPACK decoding accepts any byte string as a name or value. HTTP/2 is
+//! stricter: a request or response carrying a field that breaks the rules
+//! below is malformed and must be answered with a stream error of type
+//! PROTOCOL_ERROR (RFC 9113 Section 8.1.1). An intermediary that forwards
+//! such fields to HTTP/1.1 invites request smuggling.
+//!
+//! Run [`validate_headers`] on every decoded header list before acting on it.
+
+use crate::h2_codec::error_code;
+use crate::hpack::H2Header;
+
+/// How strictly [`validate_headers`] checks field names and values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValidationProfile {
+    /// Every requirement of RFC 9113 Section 8.2, plus the RFC 9110 grammar:
+    /// names must be tokens and values may not contain control characters
+    /// other than horizontal tab.
+    #[default]
+    Strict,
+    /// Only the checks that protect downstream HTTP/1.1 parsers: forbidden
+    /// name characters, CR/LF/NUL in values and connection-specific fields.
+    /// Values with leading or trailing whitespace, which some origins emit,
+    /// are accepted.
+    Lenient,
+}
+
+/// The rule broken by a header field, reported by [`HeaderError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderErrorKind {
+    /// The field name is empty.
+    EmptyName,
+    /// The field name contains an uppercase ASCII letter.
+    UppercaseName,
+    /// The field name contains a forbidden byte: 0x00-0x20, 0x7F-0xFF, a colon
+    /// anywhere but the first position, or (strict) a non-token character.
+    InvalidNameByte(u8),
+    /// The field value contains NUL, CR, LF or (strict) another control byte.
+    InvalidValueByte(u8),
+    /// The field value starts or ends with SP or HTAB (strict only).
+    SurroundingWhitespace,
+    /// A connection-specific field such as `connection` or `transfer-encoding`
+    /// (RFC 9113 Section 8.2.2).
+    ConnectionSpecific,
+    /// A `te` field with a value other than `trailers`.
+    InvalidTe,
+    /// A pseudo-header field followed a regular field (RFC 9113 Section 8.3).
+    PseudoAfterRegular,
+    /// A pseudo-header field appeared more than once.
+    DuplicatePseudo,
+    /// A pseudo-header field that is undefined, or not valid for a request
+    /// or response, or for this form of request.
+    UnexpectedPseudo,
+    /// A required pseudo-header field is absent.
+    MissingPseudo(&'static str),
+    /// A pseudo-header field has an invalid value, such as an empty `:path`
+    /// or a `:status` that is not three digits.
+    InvalidPseudoValue,
+}
+
+/// A header field that makes the request or response malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderError {
+    pub kind: HeaderErrorKind,
+    /// Position of the offending field in the header list, or the list length
+    /// when a required pseudo-header field is missing.
+    pub index: usize,
+}
+
+impl HeaderError {
+    /// HTTP/2 error code for the stream error (always PROTOCOL_ERROR).
+    pub fn error_code(&self) -> u32 {
+        error_code::PROTOCOL_ERROR
+    }
+}
+
+impl std::fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let HeaderErrorKind::MissingPseudo(name) = self.kind {
+            return write!(f, "malformed header list: missing {} pseudo-header", name);
+        }
+        write!(f, "malformed header field {}: ", self.index)?;
+        match self.kind {
+            HeaderErrorKind::EmptyName => write!(f, "empty name"),
+            HeaderErrorKind::UppercaseName => write!(f, "uppercase character in name"),
+            HeaderErrorKind::InvalidNameByte(b) => write!(f, "invalid byte 0x{:02x} in name", b),
+            HeaderErrorKind::InvalidValueByte(b) => write!(f, "invalid byte 0x{:02x} in value", b),
+            HeaderErrorKind::SurroundingWhitespace => write!(f, "leading or trailing whitespace in value"),
+            HeaderErrorKind::ConnectionSpecific => write!(f, "connection-specific field"),
+            HeaderErrorKind::InvalidTe => write!(f, "te field with value other than \"trailers\""),
+            HeaderErrorKind::PseudoAfterRegular => write!(f, "pseudo-header after regular field"),
+            HeaderErrorKind::DuplicatePseudo => write!(f, "duplicate pseudo-header"),
+            HeaderErrorKind::UnexpectedPseudo => write!(f, "unexpected pseudo-header"),
+            HeaderErrorKind::InvalidPseudoValue => write!(f, "invalid pseudo-header value"),
+            HeaderErrorKind::MissingPseudo(_) => unreachable!(),
+        }
+    }
+}
+
+impl std::error::Error for HeaderError {}
+
+/// Fields that only make sense on a single HTTP/1.1 connection and MUST NOT
+/// appear in HTTP/2 (RFC 9113 Section 8.2.2).
+pub(crate) const CONNECTION_SPECIFIC: &[&[u8]] = &[
+    b"connection",
+    b"proxy-connection",
+    b"keep-alive",
+    b"transfer-encoding",
+    b"upgrade",
+];
+
+/// Check every field of a decoded header list, returning the first violation.
+///
+/// Pseudo-header fields (names starting with a single `:`) are checked for
+/// valid characters only; their presence and order are checked when building
+/// an [`crate::H2Request`] or [`crate::H2Response`].
+pub fn validate_headers(headers: &[H2Header], profile: ValidationProfile) -> Result<(), HeaderError> {
+    for (index, header) in headers.iter().enumerate() {
+        validate_field(header, profile).map_err(|kind| HeaderError { kind, index })?;
+    }
+    Ok(())
+}
+
+fn validate_field(header: &H2Header, profile: ValidationProfile) -> Result<(), HeaderErrorKind> {
+    let strict = profile == ValidationProfile::Strict;
+
+    let name = header.name.as_slice();
+    let bare = name.strip_prefix(b":").unwrap_or(name);
+    if bare.is_empty() {
+        return Err(HeaderErrorKind::EmptyName);
+    }
+    for &b in bare {
+        if b.is_ascii_uppercase() {
+            return Err(HeaderErrorKind::UppercaseName);
+        }
+        if b <= 0x20 || b >= 0x7F || b == b':' || (strict && !is_token_byte(b)) {
+            return Err(HeaderErrorKind::InvalidNameByte(b));
+        }
+    }
+
+    let value = header.value.as_slice();
+    for &b in value {
+        let forbidden = matches!(b, 0x00 | b'\r' | b'\n');
+        if forbidden || (strict && (b < 0x20 && b != b'\t' || b == 0x7F)) {
+            return Err(HeaderErrorKind::InvalidValueByte(b));
+        }
+    }
+    if strict {
+        let is_ws = |b: &u8| *b == b' ' || *b == b'\t';
+        if value.first().is_some_and(is_ws) || value.last().is_some_and(is_ws) {
+            return Err(HeaderErrorKind::SurroundingWhitespace);
+        }
+    }
+
+    if CONNECTION_SPECIFIC.contains(&name) {
+        return Err(HeaderErrorKind::ConnectionSpecific);
+    }
+    if name == b"te" && !value.eq_ignore_ascii_case(b"trailers") {
+        return Err(HeaderErrorKind::InvalidTe);
+    }
+    Ok(())
+}
+
+/// `tchar` from RFC 9110 Section 5.6.2, lowercase letters only.
+fn is_token_byte(b: u8) -> bool {
+    b.is_ascii_lowercase()
+        || b.is_ascii_digit()
+        || matches!(b, b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' | b'^' | b'_' | b'`' | b'|' | b'~')
+}
@@ -0,0 +1,89 @@
+//! Tests for Alt-Svc field value parsing and serialization
+
+use h2_sans_io::{AltService, AltSvc, DEFAULT_MAX_AGE};
+
+fn services(value: &str) -> Vec<AltService> {
+    match AltSvc::parse(value.as_bytes()) {
+        Some(AltSvc::Services(services)) => services,
+        other => panic!("Expected services for {:?}, got {:?}", value, other),
+    }
+}
+
+#[test]
+fn test_clear() {
+    assert_eq!(AltSvc::parse(b"clear"), Some(AltSvc::Clear));
+    assert_eq!(AltSvc::parse(b"  clear "), Some(AltSvc::Clear));
+    assert_eq!(AltSvc::Clear.to_field_value(), "clear");
+}
+
+#[test]
+fn test_single_alternative_defaults() {
+    assert_eq!(services(r#"h3=":443""#), vec![AltService::new("h3", "", 443)]);
+}
+
+#[test]
+fn test_multiple_alternatives_with_parameters() {
+    let parsed = services(r#"h3=":443"; ma=3600; persist=1, h2="alt.example.com:8443" ; foo="bar baz""#);
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].max_age, 3600);
+    assert!(parsed[0].persist);
+    assert_eq!(parsed[1].host, "alt.example.com");
+    assert_eq!(parsed[1].port, 8443);
+    assert_eq!(parsed[1].max_age, DEFAULT_MAX_AGE);
+    assert_eq!(parsed[1].params, vec![("foo".to_string(), "bar baz".to_string())]);
+}
+
+#[test]
+fn test_ipv6_host_and_escapes() {
+    let parsed = services(r#"h2="[2001:db8::1]:443", h2="a\"b:80""#);
+    assert_eq!(parsed[0].host, "[2001:db8::1]");
+    assert_eq!(parsed[1].host, "a\"b");
+}
+
+#[test]
+fn test_percent_encoded_protocol_id() {
+    let parsed = services(r#"w%3Dx%3Ay=":443""#);
+    assert_eq!(parsed[0].protocol_id, "w=x:y");
+    assert_eq!(AltSvc::Services(parsed).to_field_value(), r#"w%3Dx%3Ay=":443""#);
+}
+
+#[test]
+fn test_lenient_parameters_and_empty_elements() {
+    let parsed = services(r#", h3=":443"; ma=soon; persist=0,, "#);
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].max_age, DEFAULT_MAX_AGE);
+    assert!(!parsed[0].persist);
+}
+
+#[test]
+fn test_invalid_values() {
+    for value in [
+        "",
+        " , ",
+        "h3",
+        "h3=:443",
+        r#"h3=":""#,
+        r#"h3="example.com""#,
+        r#"h3=":99999""#,
+        r#"h3=":443"; ma"#,
+        r#"h3=":443" h2=":443""#,
+        r#"h3=":443"#,
+        r#"h%4=":443""#,
+        "clear, clear",
+    ] {
+        assert_eq!(AltSvc::parse(value.as_bytes()), None, "{:?} accepted", value);
+    }
+}
+
+#[test]
+fn test_serialize_roundtrip() {
+    let mut h3 = AltService::new("h3", "", 443);
+    h3.max_age = 60;
+    h3.persist = true;
+    let mut h2 = AltService::new("h2", "alt.example.com", 8443);
+    h2.params.push(("note".to_string(), "a \"b\"".to_string()));
+    let value = AltSvc::Services(vec![h3, h2]);
+    let text = value.to_string();
+    assert_eq!(text, r#"h3=":443"; ma=60; persist=1, h2="alt.example.com:8443"; note="a \"b\"""#);
+    assert_eq!(AltSvc::parse(text.as_bytes()), Some(value));
+}
@@ -0,0 +1,3 @@
+//! Test module for RFC 7838 Alt-Svc field values
+
+mod field_value;
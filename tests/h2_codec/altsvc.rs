@@ -0,0 +1,93 @@
+//! Tests for RFC 7838 ALTSVC frames
+
+use h2_sans_io::{frame_type, AltSvc, H2Codec, H2Event};
+
+fn codec() -> H2Codec {
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    codec
+}
+
+fn build_frame(stream_id: u32, origin: &[u8], field_value: &[u8]) -> Vec<u8> {
+    let len = 2 + origin.len() + field_value.len();
+    let mut frame = vec![(len >> 16) as u8, (len >> 8) as u8, len as u8, frame_type::ALTSVC, 0];
+    frame.extend_from_slice(&stream_id.to_be_bytes());
+    frame.extend_from_slice(&(origin.len() as u16).to_be_bytes());
+    frame.extend_from_slice(origin);
+    frame.extend_from_slice(field_value);
+    frame
+}
+
+#[test]
+fn test_stream_zero_with_origin() {
+    let frame = build_frame(0, b"https://example.com", br#"h3=":443""#);
+    let events = codec().process(&frame).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::AltSvc {
+            stream_id: 0,
+            origin: "https://example.com".to_string(),
+            field_value: br#"h3=":443""#.to_vec(),
+        }]
+    );
+}
+
+#[test]
+fn test_stream_form_without_origin() {
+    let mut codec = codec();
+    codec.process(&H2Codec::create_headers_frame(1, &[0x82], false)).unwrap();
+    let events = codec.process(&build_frame(1, b"", b"clear")).unwrap();
+    match &events[..] {
+        [H2Event::AltSvc { stream_id: 1, origin, field_value }] => {
+            assert!(origin.is_empty());
+            assert_eq!(AltSvc::parse(field_value), Some(AltSvc::Clear));
+        }
+        other => panic!("Expected AltSvc event, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_origin_placement_ignored() {
+    // RFC 7838 Section 4: empty origin on stream 0, or an origin on a stream
+    assert!(codec().process(&build_frame(0, b"", br#"h3=":443""#)).unwrap().is_empty());
+    assert!(codec().process(&build_frame(3, b"https://example.com", br#"h3=":443""#)).unwrap().is_empty());
+}
+
+#[test]
+fn test_malformed_frames_ignored() {
+    let short = [0, 0, 1, frame_type::ALTSVC, 0, 0, 0, 0, 0, 0];
+    let mut overlong = build_frame(0, b"https://a", b"");
+    overlong[10] = 20; // origin length beyond the payload
+    let non_ascii = build_frame(0, "https://é".as_bytes(), br#"h3=":443""#);
+
+    for frame in [short.to_vec(), overlong, non_ascii] {
+        let mut codec = codec();
+        assert!(codec.process(&frame).unwrap().is_empty(), "{:?}", frame);
+        // The connection stays usable
+        let events = codec.process(&build_frame(0, b"https://a", b"clear")).unwrap();
+        assert_eq!(events.len(), 1);
+    }
+}
+
+#[test]
+fn test_create_altsvc_frame_roundtrip() {
+    let frame = H2Codec::create_altsvc_frame(0, "https://example.com", br#"h3=":443"; ma=60"#);
+    assert_eq!(frame, build_frame(0, b"https://example.com", br#"h3=":443"; ma=60"#));
+    let events = codec().process(&frame).unwrap();
+    assert!(matches!(&events[..], [H2Event::AltSvc { stream_id: 0, .. }]));
+
+    let frame = H2Codec::create_altsvc_frame(5, "", b"clear");
+    assert_eq!(frame, build_frame(5, b"", b"clear"));
+}
+
+#[test]
+#[should_panic(expected = "requires an origin")]
+fn test_create_altsvc_frame_stream_zero_without_origin_panics() {
+    H2Codec::create_altsvc_frame(0, "", b"clear");
+}
+
+#[test]
+#[should_panic(expected = "must not carry an origin")]
+fn test_create_altsvc_frame_stream_with_origin_panics() {
+    H2Codec::create_altsvc_frame(1, "https://example.com", b"clear");
+}
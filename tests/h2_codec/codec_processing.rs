@@ -94,7 +94,7 @@ fn test_codec_parse_headers() {
     let events = codec.process(&frame).unwrap();
     assert_eq!(events.len(), 1);
     match &events[0] {
-        H2Event::Headers { stream_id, header_block, end_stream } => {
+        H2Event::Headers { stream_id, header_block, end_stream, .. } => {
             assert_eq!(*stream_id, 1);
             assert_eq!(header_block, &[0x82, 0x86, 0x84, 0x41]);
             assert!(*end_stream);
@@ -292,15 +292,16 @@ fn test_unknown_frame_type_ignored() {
 }
 
 #[test]
-fn test_priority_frame_ignored() {
+fn test_priority_frame_emits_event() {
     // PRIORITY: 5 bytes (4 byte stream dep + 1 byte weight)
     let frame = build_frame(frame_type::PRIORITY, 0, 1, &[0, 0, 0, 0, 15]);
     let events = codec().process(&frame).unwrap();
-    assert!(events.is_empty());
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0], H2Event::Priority { stream_id: 1, .. }));
 }
 
 #[test]
-fn test_push_promise_ignored() {
+fn test_push_promise_without_end_headers_waits_for_continuation() {
     let frame = build_frame(frame_type::PUSH_PROMISE, 0, 1, &[0, 0, 0, 2, 0x82]);
     let events = codec().process(&frame).unwrap();
     assert!(events.is_empty());
@@ -238,11 +238,12 @@ fn test_priority_too_long_is_error() {
 }
 
 #[test]
-fn test_priority_valid_is_ignored() {
-    // Valid PRIORITY frame: 5 bytes on non-zero stream → ignored (no event)
+fn test_priority_valid_emits_event() {
+    // Valid PRIORITY frame: 5 bytes on non-zero stream → Priority event
     let frame = build_frame(frame_type::PRIORITY, 0, 1, &[0, 0, 0, 0, 15]);
     let events = codec().process(&frame).unwrap();
-    assert!(events.is_empty());
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0], H2Event::Priority { stream_id: 1, .. }));
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -260,8 +261,8 @@ fn test_h2event_partialeq_data() {
 
 #[test]
 fn test_h2event_partialeq_headers() {
-    let e1 = H2Event::Headers { stream_id: 1, header_block: vec![0x82], end_stream: false };
-    let e2 = H2Event::Headers { stream_id: 1, header_block: vec![0x82], end_stream: false };
+    let e1 = H2Event::Headers { stream_id: 1, header_block: vec![0x82], end_stream: false, priority: None };
+    let e2 = H2Event::Headers { stream_id: 1, header_block: vec![0x82], end_stream: false, priority: None };
     assert_eq!(e1, e2);
 }
 
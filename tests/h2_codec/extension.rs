@@ -0,0 +1,199 @@
+//! Tests for extension frame handlers and H2Event::Unknown
+
+use h2_sans_io::{frame_type, ExtensionEvent, ExtensionHandler, H2Codec, H2Event, H2FrameHeader};
+
+const EXT: u8 = 0xf0;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Blob {
+    flags: u8,
+    data: Vec<u8>,
+}
+
+fn codec() -> H2Codec {
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    codec
+}
+
+fn build_frame(frame_type: u8, flags: u8, stream_id: u32, payload: &[u8]) -> Vec<u8> {
+    let len = payload.len();
+    let mut frame = vec![(len >> 16) as u8, (len >> 8) as u8, len as u8, frame_type, flags];
+    frame.extend_from_slice(&stream_id.to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn blob_handler(header: &H2FrameHeader, payload: &[u8]) -> Result<Option<ExtensionEvent>, String> {
+    Ok(Some(ExtensionEvent::new(Blob { flags: header.flags, data: payload.to_vec() })))
+}
+
+/// Handler that counts frames and may interleave with header blocks on stream 0.
+#[derive(Default)]
+struct Interleaving {
+    seen: u32,
+}
+
+impl ExtensionHandler for Interleaving {
+    fn parse(&mut self, _header: &H2FrameHeader, _payload: &[u8]) -> Result<Option<ExtensionEvent>, String> {
+        self.seen += 1;
+        Ok(Some(ExtensionEvent::new(self.seen)))
+    }
+
+    fn allowed_in_header_block(&self, header: &H2FrameHeader, _pending_stream: u32) -> bool {
+        header.stream_id == 0
+    }
+}
+
+#[test]
+fn test_registered_handler_emits_typed_event() {
+    let mut codec = codec();
+    codec.register_extension(EXT, blob_handler);
+    assert!(codec.has_extension(EXT));
+    let events = codec.process(&build_frame(EXT, 0x5, 3, b"abc")).unwrap();
+    match &events[..] {
+        [H2Event::Extension { frame_type: EXT, stream_id: 3, event }] => {
+            assert!(event.is::<Blob>());
+            assert!(event.downcast_ref::<u32>().is_none());
+            assert_eq!(event.downcast_ref::<Blob>(), Some(&Blob { flags: 0x5, data: b"abc".to_vec() }));
+        }
+        other => panic!("Expected Extension event, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_extension_event_equality_and_downcast() {
+    let a = ExtensionEvent::new(Blob { flags: 0, data: vec![1] });
+    assert_eq!(a, ExtensionEvent::new(Blob { flags: 0, data: vec![1] }));
+    assert_ne!(a, ExtensionEvent::new(Blob { flags: 1, data: vec![1] }));
+    assert_ne!(ExtensionEvent::new(1u32), ExtensionEvent::new(1u64));
+
+    let a = a.downcast::<u32>().unwrap_err();
+    assert_eq!(a.downcast::<Blob>().unwrap(), Blob { flags: 0, data: vec![1] });
+}
+
+#[test]
+fn test_handler_can_drop_or_reject_frames() {
+    let mut codec = codec();
+    codec.register_extension(EXT, |_: &H2FrameHeader, payload: &[u8]| {
+        if payload.is_empty() {
+            Err("empty extension frame".to_string())
+        } else {
+            Ok(None)
+        }
+    });
+    assert!(codec.process(&build_frame(EXT, 0, 0, b"x")).unwrap().is_empty());
+    assert_eq!(codec.process(&build_frame(EXT, 0, 0, b"")).unwrap_err(), "empty extension frame");
+}
+
+#[test]
+fn test_stateful_handler_and_unregister() {
+    let mut codec = codec();
+    codec.register_extension(EXT, Interleaving::default());
+    let frame = build_frame(EXT, 0, 0, b"");
+    let events = codec.process(&[frame.clone(), frame.clone()].concat()).unwrap();
+    let counts: Vec<u32> = events
+        .iter()
+        .map(|e| match e {
+            H2Event::Extension { event, .. } => *event.downcast_ref::<u32>().unwrap(),
+            other => panic!("Expected Extension event, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(counts, vec![1, 2]);
+
+    assert!(codec.unregister_extension(EXT));
+    assert!(!codec.unregister_extension(EXT));
+    assert!(codec.process(&frame).unwrap().is_empty());
+}
+
+#[test]
+fn test_handler_overrides_builtin_extension_parsing() {
+    let mut codec = codec();
+    codec.register_extension(frame_type::ORIGIN, blob_handler);
+    let events = codec.process(&H2Codec::create_origin_frame(&["https://a"])).unwrap();
+    assert!(matches!(&events[..], [H2Event::Extension { frame_type: frame_type::ORIGIN, .. }]));
+}
+
+#[test]
+#[should_panic(expected = "core HTTP/2 frame type")]
+fn test_core_frame_type_cannot_be_registered() {
+    codec().register_extension(frame_type::PING, blob_handler);
+}
+
+#[test]
+fn test_handler_frame_rejected_during_header_block_by_default() {
+    let mut codec = codec();
+    codec.register_extension(EXT, blob_handler);
+    let frames = H2Codec::create_headers_frames(1, &[0x82; 20], false, 16);
+    codec.process(&frames[0]).unwrap();
+    let err = codec.process(&build_frame(EXT, 0, 0, b"x")).unwrap_err();
+    assert!(err.contains("CONTINUATION expected"), "{}", err);
+}
+
+#[test]
+fn test_handler_may_allow_frame_during_header_block() {
+    let mut codec = codec();
+    codec.register_extension(EXT, Interleaving::default());
+    let frames = H2Codec::create_headers_frames(1, &[0x82; 20], false, 16);
+    codec.process(&frames[0]).unwrap();
+
+    let events = codec.process(&build_frame(EXT, 0, 0, b"")).unwrap();
+    assert!(matches!(&events[..], [H2Event::Extension { stream_id: 0, .. }]));
+    let events = codec.process(&frames[1]).unwrap();
+    assert!(matches!(&events[..], [H2Event::Headers { stream_id: 1, .. }]));
+}
+
+#[test]
+fn test_handler_allow_decision_sees_frame_header() {
+    let mut codec = codec();
+    codec.register_extension(EXT, Interleaving::default());
+    codec.process(&H2Codec::create_headers_frames(1, &[0x82; 20], false, 16)[0]).unwrap();
+    // Interleaving only allows stream 0
+    assert!(codec.process(&build_frame(EXT, 0, 1, b"")).is_err());
+}
+
+#[test]
+fn test_unknown_frames_ignored_by_default() {
+    let mut codec = codec();
+    assert!(!codec.emit_unknown_frames());
+    assert!(codec.process(&build_frame(0xfe, 0, 0, b"x")).unwrap().is_empty());
+}
+
+#[test]
+fn test_unknown_frames_emitted_when_enabled() {
+    let mut codec = codec();
+    codec.set_emit_unknown_frames(true);
+    codec.register_extension(EXT, blob_handler);
+    let bytes = [build_frame(0xfe, 0x3, 7, b"raw"), build_frame(EXT, 0, 0, b"")].concat();
+    let events = codec.process(&bytes).unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0], H2Event::Unknown { frame_type: 0xfe, flags: 0x3, stream_id: 7, payload: b"raw".to_vec() });
+    assert!(matches!(events[1], H2Event::Extension { .. }));
+}
+
+#[test]
+fn test_unknown_frame_still_breaks_header_block() {
+    let mut codec = codec();
+    codec.set_emit_unknown_frames(true);
+    let frames = H2Codec::create_headers_frames(1, &[0x82; 20], false, 16);
+    codec.process(&frames[0]).unwrap();
+    assert!(codec.process(&build_frame(0xfe, 0, 0, b"")).is_err());
+}
+
+#[test]
+fn test_config_survives_reset_and_snapshot() {
+    let mut codec = codec();
+    codec.set_emit_unknown_frames(true);
+    codec.register_extension(EXT, blob_handler);
+    codec.reset();
+    assert!(codec.emit_unknown_frames());
+    assert!(codec.has_extension(EXT));
+
+    let mut restored = H2Codec::restore(&codec.snapshot()).unwrap();
+    assert!(restored.emit_unknown_frames());
+    // Handlers are not snapshotted: the frame is now unknown
+    assert!(!restored.has_extension(EXT));
+    restored.set_preface_received(true);
+    let events = restored.process(&build_frame(EXT, 0, 0, b"")).unwrap();
+    assert!(matches!(events[0], H2Event::Unknown { frame_type: EXT, .. }));
+}
@@ -283,7 +283,7 @@ fn test_create_headers_frames_roundtrip() {
         .filter(|e| matches!(e, h2_sans_io::H2Event::Headers { .. }))
         .collect();
     assert_eq!(headers_events.len(), 1);
-    if let h2_sans_io::H2Event::Headers { stream_id, header_block, end_stream } = &headers_events[0] {
+    if let h2_sans_io::H2Event::Headers { stream_id, header_block, end_stream, .. } = &headers_events[0] {
         assert_eq!(*stream_id, 1);
         assert_eq!(header_block, &block);
         assert!(*end_stream);
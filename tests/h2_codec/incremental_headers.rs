@@ -0,0 +1,174 @@
+//! Tests for incremental header block delivery (H2Event::HeaderBlockFragment)
+
+use h2_sans_io::{H2Codec, H2Event, H2Header, HpackDecoder, HpackEncoder, MAX_HEADER_BLOCK_SIZE};
+
+fn incremental_codec() -> H2Codec {
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    codec.set_incremental_headers(true);
+    codec
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let codec = H2Codec::new();
+    assert!(!codec.incremental_headers());
+}
+
+#[test]
+fn test_single_frame_block() {
+    let mut codec = incremental_codec();
+    let frame = H2Codec::create_headers_frame(1, &[0x82], true);
+    let events = codec.process(&frame).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::HeaderBlockFragment { stream_id: 1, fragment: vec![0x82], end_headers: true, end_stream: true, priority: None }]
+    );
+}
+
+#[test]
+fn test_fragment_per_frame() {
+    let mut codec = incremental_codec();
+    let frames = H2Codec::create_headers_frames(3, &[0x82; 40], false, 16);
+    assert_eq!(frames.len(), 3);
+    let mut all = Vec::new();
+    for frame in &frames {
+        all.extend(codec.process(frame).unwrap());
+    }
+    assert_eq!(all.len(), 3);
+    for (i, event) in all.iter().enumerate() {
+        match event {
+            H2Event::HeaderBlockFragment { stream_id, fragment, end_headers, end_stream, .. } => {
+                assert_eq!(*stream_id, 3);
+                assert!(!fragment.is_empty());
+                assert_eq!(*end_headers, i == 2);
+                assert!(!end_stream);
+            }
+            _ => panic!("Expected HeaderBlockFragment"),
+        }
+    }
+}
+
+#[test]
+fn test_end_stream_carried_to_continuations() {
+    let mut codec = incremental_codec();
+    let mut bytes = Vec::new();
+    for frame in H2Codec::create_headers_frames(1, &[0x82; 20], true, 16) {
+        bytes.extend(frame);
+    }
+    let events = codec.process(&bytes).unwrap();
+    assert_eq!(events.len(), 2);
+    for event in &events {
+        match event {
+            H2Event::HeaderBlockFragment { end_stream, .. } => assert!(end_stream),
+            _ => panic!("Expected HeaderBlockFragment"),
+        }
+    }
+}
+
+#[test]
+fn test_fragments_decode_incrementally() {
+    let headers = vec![
+        H2Header::new(":method", "POST"),
+        H2Header::new(":path", "/upload"),
+        H2Header::new("x-long", "v".repeat(100)),
+    ];
+    let block = HpackEncoder::new().encode(&headers);
+    let mut bytes = Vec::new();
+    for frame in H2Codec::create_headers_frames(1, &block, false, 16) {
+        bytes.extend(frame);
+    }
+
+    let mut codec = incremental_codec();
+    let mut decoder = HpackDecoder::new();
+    let mut decoded = Vec::new();
+    for event in codec.process(&bytes).unwrap() {
+        match event {
+            H2Event::HeaderBlockFragment { fragment, end_headers, .. } => {
+                decoded.extend(decoder.decode_fragment(&fragment, end_headers).unwrap());
+            }
+            _ => panic!("Expected HeaderBlockFragment"),
+        }
+    }
+    assert_eq!(decoded, headers);
+}
+
+#[test]
+fn test_interleaved_frame_still_rejected() {
+    let mut codec = incremental_codec();
+    let frames = H2Codec::create_headers_frames(1, &[0x82; 20], false, 16);
+    codec.process(&frames[0]).unwrap();
+    let ping = [0, 0, 8, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    assert!(codec.process(&ping).is_err());
+}
+
+#[test]
+fn test_continuation_wrong_stream_rejected() {
+    let mut codec = incremental_codec();
+    let frames = H2Codec::create_headers_frames(1, &[0x82; 20], false, 16);
+    codec.process(&frames[0]).unwrap();
+    let cont = H2Codec::create_continuation_frame(3, &[0x82], true);
+    assert!(codec.process(&cont).is_err());
+}
+
+#[test]
+fn test_block_size_limit_enforced_without_buffering() {
+    let mut codec = incremental_codec();
+    let chunk = vec![0x82; 16_384];
+    let first = H2Codec::create_continuation_frame(1, &chunk, false);
+    // HEADERS without END_HEADERS: turn the CONTINUATION header into HEADERS
+    let mut headers = first.clone();
+    headers[3] = 0x1;
+    codec.process(&headers).unwrap();
+    let mut total = chunk.len();
+    loop {
+        let result = codec.process(&first);
+        total += chunk.len();
+        if total > MAX_HEADER_BLOCK_SIZE {
+            let err = result.unwrap_err();
+            assert!(err.contains("Header block too large"), "{}", err);
+            break;
+        }
+        assert_eq!(result.unwrap().len(), 1);
+    }
+}
+
+#[test]
+fn test_oversized_first_fragment_rejected() {
+    let block = vec![0x82; MAX_HEADER_BLOCK_SIZE + 1];
+    let mut headers = H2Codec::create_continuation_frame(1, &block, false);
+    headers[3] = 0x1;
+    for incremental in [true, false] {
+        let mut codec = incremental_codec();
+        codec.set_incremental_headers(incremental);
+        let err = codec.process(&headers).unwrap_err();
+        assert!(err.contains("Header block too large"), "{}", err);
+    }
+}
+
+#[test]
+fn test_headers_complete_tracked() {
+    let mut codec = incremental_codec();
+    for frame in H2Codec::create_headers_frames(5, &[0x82; 20], false, 16) {
+        codec.process(&frame).unwrap();
+    }
+    // The stream accepts DATA after the block completes
+    let data = H2Codec::create_data_frames(5, b"body", true, 16_384);
+    let events = codec.process(&data[0]).unwrap();
+    assert!(matches!(events[0], H2Event::Data { stream_id: 5, .. }));
+}
+
+#[test]
+fn test_mode_survives_reset_and_snapshot() {
+    let mut codec = incremental_codec();
+    let frames = H2Codec::create_headers_frames(1, &[0x82; 20], false, 16);
+    codec.process(&frames[0]).unwrap();
+
+    let mut restored = H2Codec::restore(&codec.snapshot()).unwrap();
+    assert!(restored.incremental_headers());
+    let events = restored.process(&frames[1]).unwrap();
+    assert!(matches!(events[0], H2Event::HeaderBlockFragment { end_headers: true, .. }));
+
+    codec.reset();
+    assert!(codec.incremental_headers());
+}
@@ -1,17 +1,27 @@
 //! Test module for h2_codec
 
+mod altsvc;
 mod codec_processing;
 mod continuation;
 mod continuation_advanced;
 mod edge_cases;
 mod error_recovery;
+mod extension;
 mod frame_building;
 mod frame_builder_validation;
 mod frame_parsing;
+mod incremental_headers;
 mod memory_and_lifecycle;
+mod origin;
 mod padding_exhaustive;
 mod preface_edge_cases;
+mod priority;
+mod priority_update;
 mod protocol_frames;
+mod push_acceptance;
+mod push_builder;
+mod push_promise;
 mod rfc_compliance;
 mod roundtrip_comprehensive;
-mod stream_state;
+mod snapshot;
+mod stream_state;
@@ -0,0 +1,78 @@
+//! Tests for RFC 8336 ORIGIN frames
+
+use h2_sans_io::{frame_type, H2Codec, H2Event};
+
+fn codec() -> H2Codec {
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    codec
+}
+
+fn build_frame(stream_id: u32, payload: &[u8]) -> Vec<u8> {
+    let len = payload.len();
+    let mut frame = vec![(len >> 16) as u8, (len >> 8) as u8, len as u8, frame_type::ORIGIN, 0];
+    frame.extend_from_slice(&stream_id.to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+#[test]
+fn test_origin_set_parsed() {
+    let mut payload = vec![0, 19];
+    payload.extend_from_slice(b"https://example.com");
+    payload.extend_from_slice(&[0, 16]);
+    payload.extend_from_slice(b"https://a.b:8443");
+    let events = codec().process(&build_frame(0, &payload)).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::Origin { origins: vec!["https://example.com".to_string(), "https://a.b:8443".to_string()] }]
+    );
+}
+
+#[test]
+fn test_empty_origin_set() {
+    let events = codec().process(&build_frame(0, &[])).unwrap();
+    assert_eq!(events, vec![H2Event::Origin { origins: Vec::new() }]);
+}
+
+#[test]
+fn test_origin_on_stream_ignored() {
+    let mut payload = vec![0, 19];
+    payload.extend_from_slice(b"https://example.com");
+    assert!(codec().process(&build_frame(1, &payload)).unwrap().is_empty());
+}
+
+#[test]
+fn test_truncated_origin_frames_ignored() {
+    for payload in [vec![0], vec![0, 5, b'h', b't'], vec![0, 1, b'a', 0]] {
+        let mut codec = codec();
+        assert!(codec.process(&build_frame(0, &payload)).unwrap().is_empty(), "{:?}", payload);
+        // The connection stays usable
+        let ping = [0, 0, 8, 6, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(codec.process(&ping).unwrap().len(), 1);
+    }
+}
+
+#[test]
+fn test_non_ascii_origin_entry_skipped() {
+    let mut payload = vec![0, 2, 0xC3, 0xA9, 0, 9];
+    payload.extend_from_slice(b"https://a");
+    let events = codec().process(&build_frame(0, &payload)).unwrap();
+    assert_eq!(events, vec![H2Event::Origin { origins: vec!["https://a".to_string()] }]);
+}
+
+#[test]
+fn test_create_origin_frame_roundtrip() {
+    let origins = ["https://example.com", "https://cdn.example.com"];
+    let frame = H2Codec::create_origin_frame(&origins);
+    assert_eq!(&frame[3..9], &[frame_type::ORIGIN, 0, 0, 0, 0, 0]);
+    assert_eq!(frame.len(), 9 + 2 + 19 + 2 + 23);
+    let events = codec().process(&frame).unwrap();
+    assert_eq!(events, vec![H2Event::Origin { origins: origins.iter().map(|o| o.to_string()).collect() }]);
+}
+
+#[test]
+#[should_panic(expected = "not ASCII")]
+fn test_create_origin_frame_non_ascii_panics() {
+    H2Codec::create_origin_frame(&["https://exämple.com"]);
+}
@@ -0,0 +1,136 @@
+//! Tests for PRIORITY frames and HEADERS priority fields (H2Event::Priority)
+
+use h2_sans_io::{error_code, flags, frame_type, H2Codec, H2Event, StreamPriority};
+
+fn codec() -> H2Codec {
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    codec
+}
+
+fn build_frame(frame_type: u8, flags: u8, stream_id: u32, payload: &[u8]) -> Vec<u8> {
+    let len = payload.len();
+    let mut frame = vec![(len >> 16) as u8, (len >> 8) as u8, len as u8, frame_type, flags];
+    frame.extend_from_slice(&stream_id.to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+const SPEC: StreamPriority = StreamPriority { exclusive: true, dependency: 3, weight: 219 };
+
+#[test]
+fn test_priority_fields_decoded() {
+    let frame = build_frame(frame_type::PRIORITY, 0, 5, &[0x00, 0, 0, 7, 15]);
+    let events = codec().process(&frame).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::Priority { stream_id: 5, priority: StreamPriority { exclusive: false, dependency: 7, weight: 15 } }]
+    );
+}
+
+#[test]
+fn test_effective_weight() {
+    assert_eq!(StreamPriority { exclusive: false, dependency: 0, weight: 0 }.effective_weight(), 1);
+    assert_eq!(StreamPriority { exclusive: false, dependency: 0, weight: 255 }.effective_weight(), 256);
+}
+
+#[test]
+fn test_headers_carry_priority() {
+    let frame = build_frame(frame_type::HEADERS, flags::END_HEADERS | flags::PRIORITY, 5, &[0x80, 0, 0, 3, 219, 0x82]);
+    let events = codec().process(&frame).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::Headers { stream_id: 5, header_block: vec![0x82], end_stream: false, priority: Some(SPEC) }]
+    );
+}
+
+#[test]
+fn test_headers_without_priority_flag() {
+    let frame = H2Codec::create_headers_frame(1, &[0x82], true);
+    match &codec().process(&frame).unwrap()[0] {
+        H2Event::Headers { priority, .. } => assert_eq!(*priority, None),
+        other => panic!("Expected Headers, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_priority_kept_across_continuation() {
+    let mut codec = codec();
+    let headers = build_frame(frame_type::HEADERS, flags::PRIORITY, 5, &[0x80, 0, 0, 3, 219, 0x82]);
+    assert!(codec.process(&headers).unwrap().is_empty());
+    let events = codec.process(&H2Codec::create_continuation_frame(5, &[0x86], true)).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::Headers { stream_id: 5, header_block: vec![0x82, 0x86], end_stream: false, priority: Some(SPEC) }]
+    );
+}
+
+#[test]
+fn test_priority_survives_snapshot_mid_block() {
+    let mut codec = codec();
+    let headers = build_frame(frame_type::HEADERS, flags::PRIORITY, 5, &[0x80, 0, 0, 3, 219, 0x82]);
+    codec.process(&headers).unwrap();
+    let mut restored = H2Codec::restore(&codec.snapshot()).unwrap();
+    let events = restored.process(&H2Codec::create_continuation_frame(5, &[0x86], true)).unwrap();
+    assert!(matches!(events[0], H2Event::Headers { priority: Some(SPEC), .. }));
+}
+
+#[test]
+fn test_incremental_fragment_carries_priority() {
+    let mut codec = codec();
+    codec.set_incremental_headers(true);
+    let headers = build_frame(frame_type::HEADERS, flags::PRIORITY, 5, &[0x80, 0, 0, 3, 219, 0x82]);
+    let first = codec.process(&headers).unwrap();
+    assert!(matches!(first[0], H2Event::HeaderBlockFragment { priority: Some(SPEC), .. }));
+    let second = codec.process(&H2Codec::create_continuation_frame(5, &[0x86], true)).unwrap();
+    assert!(matches!(second[0], H2Event::HeaderBlockFragment { priority: None, .. }));
+}
+
+#[test]
+fn test_priority_self_dependency_is_stream_error() {
+    let mut codec = codec();
+    let frame = build_frame(frame_type::PRIORITY, 0, 5, &[0, 0, 0, 5, 15]);
+    let events = codec.process(&frame).unwrap();
+    assert_eq!(events, vec![H2Event::StreamError { stream_id: 5, error_code: error_code::PROTOCOL_ERROR }]);
+
+    // The connection is still usable.
+    let ping = build_frame(frame_type::PING, 0, 0, &[0; 8]);
+    assert_eq!(codec.process(&ping).unwrap().len(), 1);
+}
+
+#[test]
+fn test_headers_self_dependency_still_delivers_block() {
+    let frame = build_frame(frame_type::HEADERS, flags::END_HEADERS | flags::PRIORITY, 5, &[0, 0, 0, 5, 15, 0x82]);
+    let events = codec().process(&frame).unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(matches!(&events[0], H2Event::Headers { stream_id: 5, header_block, .. } if header_block == &[0x82]));
+    assert_eq!(events[1], H2Event::StreamError { stream_id: 5, error_code: error_code::PROTOCOL_ERROR });
+}
+
+#[test]
+fn test_create_priority_frame_roundtrip() {
+    let frame = H2Codec::create_priority_frame(5, SPEC);
+    assert_eq!(frame, build_frame(frame_type::PRIORITY, 0, 5, &[0x80, 0, 0, 3, 219]));
+    assert_eq!(codec().process(&frame).unwrap(), vec![H2Event::Priority { stream_id: 5, priority: SPEC }]);
+}
+
+#[test]
+fn test_create_headers_frame_with_priority_roundtrip() {
+    let frame = H2Codec::create_headers_frame_with_priority(5, &[0x82, 0x86], true, SPEC);
+    assert_eq!(
+        codec().process(&frame).unwrap(),
+        vec![H2Event::Headers { stream_id: 5, header_block: vec![0x82, 0x86], end_stream: true, priority: Some(SPEC) }]
+    );
+}
+
+#[test]
+#[should_panic(expected = "cannot depend on itself")]
+fn test_create_priority_frame_self_dependency_panics() {
+    H2Codec::create_priority_frame(3, SPEC);
+}
+
+#[test]
+#[should_panic(expected = "non-zero stream ID")]
+fn test_create_priority_frame_stream_zero_panics() {
+    H2Codec::create_priority_frame(0, SPEC);
+}
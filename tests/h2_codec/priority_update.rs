@@ -0,0 +1,108 @@
+//! Tests for RFC 9218 PRIORITY_UPDATE frames and SETTINGS_NO_RFC7540_PRIORITIES
+
+use h2_sans_io::{frame_type, settings_id, ExtensiblePriority, H2Codec, H2Event, Role};
+
+fn codec() -> H2Codec {
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    codec
+}
+
+fn build_frame(frame_type: u8, flags: u8, stream_id: u32, payload: &[u8]) -> Vec<u8> {
+    let len = payload.len();
+    let mut frame = vec![(len >> 16) as u8, (len >> 8) as u8, len as u8, frame_type, flags];
+    frame.extend_from_slice(&stream_id.to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+#[test]
+fn test_priority_update_parsed() {
+    let frame = build_frame(frame_type::PRIORITY_UPDATE, 0, 0, b"\x00\x00\x00\x05u=1, i");
+    let events = codec().process(&frame).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::PriorityUpdate { prioritized_stream_id: 5, field_value: b"u=1, i".to_vec() }]
+    );
+    if let H2Event::PriorityUpdate { field_value, .. } = &events[0] {
+        assert_eq!(ExtensiblePriority::parse(field_value), Some(ExtensiblePriority { urgency: 1, incremental: true }));
+    }
+}
+
+#[test]
+fn test_priority_update_empty_field_value() {
+    let frame = build_frame(frame_type::PRIORITY_UPDATE, 0, 0, &[0x80, 0, 0, 3]);
+    let events = codec().process(&frame).unwrap();
+    // Reserved bit is ignored.
+    assert_eq!(events, vec![H2Event::PriorityUpdate { prioritized_stream_id: 3, field_value: Vec::new() }]);
+}
+
+#[test]
+fn test_priority_update_on_stream_is_error() {
+    let frame = build_frame(frame_type::PRIORITY_UPDATE, 0, 1, b"\x00\x00\x00\x01u=1");
+    assert!(codec().process(&frame).unwrap_err().contains("non-zero stream"));
+}
+
+#[test]
+fn test_priority_update_for_stream_zero_is_error() {
+    let frame = build_frame(frame_type::PRIORITY_UPDATE, 0, 0, b"\x00\x00\x00\x00u=1");
+    assert!(codec().process(&frame).is_err());
+}
+
+#[test]
+fn test_priority_update_received_by_client_is_error() {
+    let frame = build_frame(frame_type::PRIORITY_UPDATE, 0, 0, b"\x00\x00\x00\x01u=1");
+    let mut client = codec();
+    client.set_role(Role::Client);
+    assert!(client.process(&frame).unwrap_err().contains("PROTOCOL_ERROR"));
+
+    let mut server = codec();
+    server.set_role(Role::Server);
+    assert_eq!(server.process(&frame).unwrap().len(), 1);
+}
+
+#[test]
+fn test_priority_update_too_short_is_error() {
+    let frame = build_frame(frame_type::PRIORITY_UPDATE, 0, 0, &[0, 0, 1]);
+    assert!(codec().process(&frame).unwrap_err().contains("frame size error"));
+}
+
+#[test]
+fn test_create_priority_update_roundtrip() {
+    let frame = H2Codec::create_priority_update_frame(7, b"u=0");
+    assert_eq!(frame, build_frame(frame_type::PRIORITY_UPDATE, 0, 0, b"\x00\x00\x00\x07u=0"));
+    assert_eq!(
+        codec().process(&frame).unwrap(),
+        vec![H2Event::PriorityUpdate { prioritized_stream_id: 7, field_value: b"u=0".to_vec() }]
+    );
+}
+
+#[test]
+#[should_panic(expected = "non-zero prioritized stream ID")]
+fn test_create_priority_update_stream_zero_panics() {
+    H2Codec::create_priority_update_frame(0, b"u=0");
+}
+
+#[test]
+fn test_no_rfc7540_priorities_setting() {
+    let frame = H2Codec::create_settings_frame(&[(settings_id::NO_RFC7540_PRIORITIES, 1)]);
+    assert_eq!(frame, build_frame(frame_type::SETTINGS, 0, 0, &[0, 9, 0, 0, 0, 1]));
+    let events = codec().process(&frame).unwrap();
+    assert_eq!(events, vec![H2Event::Settings { ack: false, settings: vec![(settings_id::NO_RFC7540_PRIORITIES, 1)] }]);
+}
+
+#[test]
+fn test_no_rfc7540_priorities_invalid_value_is_error() {
+    let frame = H2Codec::create_settings_frame(&[(settings_id::NO_RFC7540_PRIORITIES, 2)]);
+    assert!(codec().process(&frame).unwrap_err().contains("NO_RFC7540_PRIORITIES"));
+}
+
+#[test]
+fn test_create_settings_frame_multiple() {
+    let frame = H2Codec::create_settings_frame(&[(settings_id::MAX_CONCURRENT_STREAMS, 100), (settings_id::INITIAL_WINDOW_SIZE, 1 << 20)]);
+    let events = codec().process(&frame).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::Settings { ack: false, settings: vec![(settings_id::MAX_CONCURRENT_STREAMS, 100), (settings_id::INITIAL_WINDOW_SIZE, 1 << 20)] }]
+    );
+}
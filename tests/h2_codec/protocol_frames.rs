@@ -1,6 +1,6 @@
 //! Tests for HTTP/2 protocol frames (PING, WINDOW_UPDATE, SETTINGS)
 
-use h2_sans_io::{H2Codec, H2Event, settings_id};
+use h2_sans_io::{H2Codec, H2Event, StreamPriority, settings_id};
 
 fn with_preface(codec: &mut H2Codec) {
     codec.set_preface_received(true);
@@ -168,14 +168,19 @@ fn test_settings_parsing_unknown_setting_ignored() {
 }
 
 #[test]
-fn test_priority_frame_ignored() {
-    // PRIORITY frames (type 0x2) should be ignored
+fn test_priority_frame_parsing() {
+    // PRIORITY frame (type 0x2): exclusive dependency on stream 0, weight 128
     let mut codec = H2Codec::new();
     with_preface(&mut codec);
-    let frame = vec![0, 0, 5, 2, 0, 0, 0, 0, 1, 0, 0, 0, 0, 128];
+    let frame = vec![0, 0, 5, 2, 0, 0, 0, 0, 1, 0x80, 0, 0, 0, 128];
     let events = codec.process(&frame).unwrap();
-    // PRIORITY should be silently ignored (no event)
-    assert!(events.is_empty());
+    assert_eq!(
+        events,
+        vec![H2Event::Priority {
+            stream_id: 1,
+            priority: StreamPriority { exclusive: true, dependency: 0, weight: 128 },
+        }]
+    );
 }
 
 #[test]
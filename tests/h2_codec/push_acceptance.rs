@@ -0,0 +1,90 @@
+//! Tests for PUSH_PROMISE acceptance by role and ENABLE_PUSH, and refusing pushes
+
+use h2_sans_io::{error_code, flags, frame_type, H2Codec, H2Event, Role};
+
+fn codec() -> H2Codec {
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    codec
+}
+
+fn push_promise(stream_id: u32, promised: u32) -> Vec<u8> {
+    let mut frame = vec![0, 0, 5, frame_type::PUSH_PROMISE, flags::END_HEADERS];
+    frame.extend_from_slice(&stream_id.to_be_bytes());
+    frame.extend_from_slice(&promised.to_be_bytes());
+    frame.push(0x82);
+    frame
+}
+
+#[test]
+fn test_push_accepted_by_default() {
+    let mut codec = codec();
+    assert!(codec.enable_push());
+    assert_eq!(codec.role(), None);
+    assert_eq!(codec.process(&push_promise(1, 2)).unwrap().len(), 1);
+}
+
+#[test]
+fn test_client_accepts_push() {
+    let mut codec = codec();
+    codec.set_role(Role::Client);
+    assert!(codec.enable_push());
+    assert_eq!(codec.process(&push_promise(1, 2)).unwrap().len(), 1);
+}
+
+#[test]
+fn test_server_rejects_push() {
+    let mut codec = codec();
+    codec.set_role(Role::Server);
+    assert!(!codec.enable_push());
+    let err = codec.process(&push_promise(1, 2)).unwrap_err();
+    assert!(err.contains("PROTOCOL_ERROR"));
+    assert!(err.contains("server"));
+}
+
+#[test]
+fn test_disabled_push_rejected() {
+    let mut codec = codec();
+    codec.set_role(Role::Client);
+    codec.set_enable_push(false);
+    assert!(!codec.enable_push());
+    let err = codec.process(&push_promise(1, 2)).unwrap_err();
+    assert!(err.contains("PROTOCOL_ERROR"));
+    assert!(codec.stream_state(2).is_none());
+
+    codec.set_enable_push(true);
+    codec.reset();
+    codec.set_preface_received(true);
+    assert_eq!(codec.process(&push_promise(1, 2)).unwrap().len(), 1);
+}
+
+#[test]
+fn test_settings_kept_across_reset_and_snapshot() {
+    let mut codec = codec();
+    codec.set_role(Role::Server);
+    codec.set_enable_push(false);
+    codec.reset();
+    assert_eq!(codec.role(), Some(Role::Server));
+
+    let restored = H2Codec::restore(&codec.snapshot()).unwrap();
+    assert_eq!(restored.role(), Some(Role::Server));
+    assert!(!restored.enable_push());
+}
+
+#[test]
+fn test_refuse_push() {
+    let mut codec = codec();
+    codec.set_role(Role::Client);
+    let events = codec.process(&push_promise(1, 2)).unwrap();
+    let promised = match events[0] {
+        H2Event::PushPromise { promised_stream_id, .. } => promised_stream_id,
+        ref other => panic!("Expected PushPromise, got {:?}", other),
+    };
+    let rst = codec.refuse_push(promised, error_code::REFUSED_STREAM);
+    assert_eq!(rst, H2Codec::create_rst_stream(2, error_code::REFUSED_STREAM));
+    assert!(codec.stream_state(2).is_none());
+
+    codec.process(&push_promise(1, 4)).unwrap();
+    let rst = codec.refuse_push(4, error_code::CANCEL);
+    assert_eq!(&rst[9..], &error_code::CANCEL.to_be_bytes());
+}
@@ -0,0 +1,201 @@
+//! Tests for PUSH_PROMISE builders and server-side promised stream allocation
+
+use h2_sans_io::{
+    error_code, flags, frame_type, settings_id, H2Codec, H2Event, H2Header, HpackDecoder, HpackEncoder, PushError,
+    Role,
+};
+
+/// A server that has received the requests on streams 1 and 3.
+fn server() -> H2Codec {
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    codec.set_role(Role::Server);
+    open_requests(&mut codec);
+    codec
+}
+
+fn open_requests(codec: &mut H2Codec) {
+    for stream_id in [1, 3] {
+        codec.process(&H2Codec::create_headers_frames(stream_id, &[0x82], false, 16384)[0]).unwrap();
+    }
+}
+
+fn client() -> H2Codec {
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    codec.set_role(Role::Client);
+    codec
+}
+
+fn pushed_request() -> Vec<H2Header> {
+    vec![
+        H2Header::new(":method", "GET"),
+        H2Header::new(":scheme", "https"),
+        H2Header::new(":authority", "example.com"),
+        H2Header::new(":path", "/critical.css"),
+    ]
+}
+
+#[test]
+fn test_single_frame_push_promise() {
+    let frames = H2Codec::create_push_promise_frames(1, 2, &[0x82, 0x87], 16384);
+    assert_eq!(frames, vec![vec![0, 0, 6, frame_type::PUSH_PROMISE, flags::END_HEADERS, 0, 0, 0, 1, 0, 0, 0, 2, 0x82, 0x87]]);
+}
+
+#[test]
+fn test_split_into_continuation() {
+    let block = vec![0x82; 20];
+    let frames = H2Codec::create_push_promise_frames(1, 2, &block, 8);
+    // 4 bytes in the PUSH_PROMISE (after the promised ID), then 8 + 8
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0][3], frame_type::PUSH_PROMISE);
+    assert_eq!(frames[0][4], 0);
+    assert_eq!(frames[0].len(), 9 + 8);
+    assert_eq!(frames[1][3], frame_type::CONTINUATION);
+    assert_eq!(frames[1][4], 0);
+    assert_eq!(frames[2][4], flags::END_HEADERS);
+
+    let mut receiver = client();
+    let mut events = Vec::new();
+    for frame in &frames {
+        events.extend(receiver.process(frame).unwrap());
+    }
+    assert_eq!(events, vec![H2Event::PushPromise { stream_id: 1, promised_stream_id: 2, header_block: block }]);
+}
+
+#[test]
+fn test_empty_header_block() {
+    let frames = H2Codec::create_push_promise_frames(1, 2, &[], 16384);
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0][4], flags::END_HEADERS);
+}
+
+#[test]
+#[should_panic(expected = "no room")]
+fn test_tiny_max_frame_size_panics() {
+    H2Codec::create_push_promise_frames(1, 2, &[0x82], 4);
+}
+
+#[test]
+fn test_push_promise_allocates_even_ids() {
+    let mut codec = server();
+    let block = HpackEncoder::new().encode(&pushed_request());
+    let (first, frames) = codec.push_promise(1, &block, 16384).unwrap();
+    let (second, _) = codec.push_promise(3, &block, 16384).unwrap();
+    assert_eq!((first, second), (2, 4));
+    assert_eq!(codec.pushed_stream_count(), 2);
+
+    let events = client().process(&frames[0]).unwrap();
+    match &events[..] {
+        [H2Event::PushPromise { stream_id: 1, promised_stream_id: 2, header_block }] => {
+            assert_eq!(HpackDecoder::new().decode(header_block).unwrap(), pushed_request());
+        }
+        other => panic!("Expected PushPromise, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_peer_disabled_push() {
+    let mut codec = server();
+    codec.process(&H2Codec::create_settings_frame(&[(settings_id::ENABLE_PUSH, 0)])).unwrap();
+    assert_eq!(codec.push_promise(1, &[0x82], 16384), Err(PushError::PushDisabled));
+
+    codec.process(&H2Codec::create_settings_frame(&[(settings_id::ENABLE_PUSH, 1)])).unwrap();
+    assert!(codec.push_promise(1, &[0x82], 16384).is_ok());
+}
+
+#[test]
+fn test_peer_max_concurrent_streams() {
+    let mut codec = server();
+    codec.process(&H2Codec::create_settings_frame(&[(settings_id::MAX_CONCURRENT_STREAMS, 2)])).unwrap();
+    let (a, _) = codec.push_promise(1, &[0x82], 16384).unwrap();
+    let (b, _) = codec.push_promise(1, &[0x82], 16384).unwrap();
+    let (c, _) = codec.push_promise(1, &[0x82], 16384).unwrap();
+    codec.open_pushed_stream(a).unwrap();
+    codec.open_pushed_stream(b).unwrap();
+    let err = codec.open_pushed_stream(c).unwrap_err();
+    assert_eq!(err, PushError::ConcurrencyLimit(2));
+    assert_eq!(err.to_string(), "peer allows at most 2 concurrent streams");
+    assert_eq!(codec.open_pushed_stream(a), Ok(()));
+    assert_eq!(codec.open_pushed_stream_count(), 2);
+
+    codec.remove_stream(a);
+    assert_eq!(codec.open_pushed_stream(c), Ok(()));
+    assert_eq!((codec.pushed_stream_count(), codec.open_pushed_stream_count()), (2, 2));
+}
+
+#[test]
+fn test_reserved_pushes_not_limited() {
+    // RFC 9113 Section 5.1.2: reserved streams are not counted
+    let mut codec = server();
+    codec.process(&H2Codec::create_settings_frame(&[(settings_id::MAX_CONCURRENT_STREAMS, 1)])).unwrap();
+    let (a, _) = codec.push_promise(1, &[0x82], 16384).unwrap();
+    let (b, _) = codec.push_promise(1, &[0x82], 16384).unwrap();
+    assert_eq!(codec.open_pushed_stream_count(), 0);
+
+    // Once the open pushes reach the limit, no new promise is made
+    codec.open_pushed_stream(a).unwrap();
+    assert_eq!(codec.push_promise(1, &[0x82], 16384), Err(PushError::ConcurrencyLimit(1)));
+    assert_eq!(codec.open_pushed_stream(b), Err(PushError::ConcurrencyLimit(1)));
+}
+
+#[test]
+fn test_open_unpromised_stream() {
+    let err = server().open_pushed_stream(2).unwrap_err();
+    assert_eq!(err, PushError::NotPromised(2));
+    assert_eq!(err.to_string(), "stream 2 is not a promised stream");
+}
+
+#[test]
+fn test_push_state_survives_snapshot() {
+    let mut codec = server();
+    codec.process(&H2Codec::create_settings_frame(&[(settings_id::MAX_CONCURRENT_STREAMS, 1)])).unwrap();
+    codec.push_promise(1, &[0x82], 16384).unwrap();
+    codec.push_promise(1, &[0x82], 16384).unwrap();
+    codec.open_pushed_stream(2).unwrap();
+    let mut restored = H2Codec::restore(&codec.snapshot()).unwrap();
+    assert_eq!((restored.pushed_stream_count(), restored.open_pushed_stream_count()), (2, 1));
+    assert_eq!(restored.open_pushed_stream(4), Err(PushError::ConcurrencyLimit(1)));
+    restored.remove_stream(2);
+    assert_eq!(restored.open_pushed_stream(4), Ok(()));
+    assert_eq!(restored.push_promise(1, &[0x82], 16384), Err(PushError::ConcurrencyLimit(1)));
+    restored.remove_stream(4);
+    assert_eq!(restored.push_promise(1, &[0x82], 16384).unwrap().0, 6);
+}
+
+#[test]
+fn test_reset_clears_push_state() {
+    let mut codec = server();
+    codec.process(&H2Codec::create_settings_frame(&[(settings_id::ENABLE_PUSH, 0)])).unwrap();
+    codec.reset();
+    codec.set_preface_received(true);
+    open_requests(&mut codec);
+    assert_eq!(codec.push_promise(1, &[0x82], 16384).unwrap().0, 2);
+}
+
+#[test]
+fn test_push_promise_requires_server_role() {
+    assert_eq!(client().push_promise(1, &[0x82], 16384), Err(PushError::NotServer));
+    let mut codec = H2Codec::new();
+    assert_eq!(codec.push_promise(1, &[0x82], 16384), Err(PushError::NotServer));
+}
+
+#[test]
+fn test_push_promise_requires_open_client_stream() {
+    let mut codec = server();
+    // Even, never opened, and pushed streams cannot carry a promise
+    for stream_id in [2, 5] {
+        assert_eq!(codec.push_promise(stream_id, &[0x82], 16384), Err(PushError::StreamNotOpen(stream_id)));
+    }
+    let (promised, _) = codec.push_promise(1, &[0x82], 16384).unwrap();
+    assert_eq!(codec.push_promise(promised, &[0x82], 16384), Err(PushError::StreamNotOpen(promised)));
+
+    // Half-closed (remote) is fine; reset or removed streams are not
+    codec.process(&H2Codec::create_data_frames(1, b"", true, 16384)[0]).unwrap();
+    assert!(codec.push_promise(1, &[0x82], 16384).is_ok());
+    codec.process(&H2Codec::create_rst_stream(1, error_code::CANCEL)).unwrap();
+    assert_eq!(codec.push_promise(1, &[0x82], 16384), Err(PushError::StreamNotOpen(1)));
+    codec.remove_stream(3);
+    let err = codec.push_promise(3, &[0x82], 16384).unwrap_err();
+    assert_eq!(err.to_string(), "stream 3 is not an open client stream");
+}
@@ -0,0 +1,158 @@
+//! Tests for PUSH_PROMISE parsing and reserved (remote) stream tracking
+
+use h2_sans_io::{flags, frame_type, H2Codec, H2Event, H2Header, HpackDecoder, HpackEncoder};
+
+fn codec() -> H2Codec {
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    codec
+}
+
+fn build_frame(frame_type: u8, flags: u8, stream_id: u32, payload: &[u8]) -> Vec<u8> {
+    let len = payload.len();
+    let mut frame = vec![(len >> 16) as u8, (len >> 8) as u8, len as u8, frame_type, flags];
+    frame.extend_from_slice(&stream_id.to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn push_promise(stream_id: u32, promised: u32, block: &[u8], flags: u8) -> Vec<u8> {
+    let mut payload = promised.to_be_bytes().to_vec();
+    payload.extend_from_slice(block);
+    build_frame(frame_type::PUSH_PROMISE, flags, stream_id, &payload)
+}
+
+#[test]
+fn test_single_frame_push_promise() {
+    let mut codec = codec();
+    let events = codec.process(&push_promise(1, 2, &[0x82, 0x87], flags::END_HEADERS)).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::PushPromise { stream_id: 1, promised_stream_id: 2, header_block: vec![0x82, 0x87] }]
+    );
+    assert!(codec.stream_state(2).unwrap().reserved_remote);
+}
+
+#[test]
+fn test_padded_push_promise() {
+    // pad_length=3, promised stream 4, block 0x82, padding
+    let payload = [3, 0, 0, 0, 4, 0x82, 0, 0, 0];
+    let frame = build_frame(frame_type::PUSH_PROMISE, flags::END_HEADERS | flags::PADDED, 1, &payload);
+    let events = codec().process(&frame).unwrap();
+    assert_eq!(events, vec![H2Event::PushPromise { stream_id: 1, promised_stream_id: 4, header_block: vec![0x82] }]);
+}
+
+#[test]
+fn test_push_promise_with_continuation() {
+    let mut codec = codec();
+    assert!(codec.process(&push_promise(1, 2, &[0x82], 0)).unwrap().is_empty());
+    let events = codec.process(&H2Codec::create_continuation_frame(1, &[0x87], true)).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::PushPromise { stream_id: 1, promised_stream_id: 2, header_block: vec![0x82, 0x87] }]
+    );
+    // The CONTINUATION sequence is over; other frames are accepted again.
+    assert_eq!(codec.process(&H2Codec::create_headers_frame(2, &[0x88], false)).unwrap().len(), 1);
+}
+
+#[test]
+fn test_push_promise_assembled_in_incremental_mode() {
+    let mut codec = codec();
+    codec.set_incremental_headers(true);
+    codec.process(&push_promise(1, 2, &[0x82], 0)).unwrap();
+    let events = codec.process(&H2Codec::create_continuation_frame(1, &[0x87], true)).unwrap();
+    assert!(matches!(&events[0], H2Event::PushPromise { header_block, .. } if header_block == &[0x82, 0x87]));
+}
+
+#[test]
+fn test_push_promise_interlock() {
+    let mut codec = codec();
+    codec.process(&push_promise(1, 2, &[0x82], 0)).unwrap();
+    let ping = build_frame(frame_type::PING, 0, 0, &[0; 8]);
+    assert!(codec.process(&ping).is_err());
+}
+
+#[test]
+fn test_response_headers_leave_reserved_state() {
+    let mut codec = codec();
+    codec.process(&push_promise(1, 2, &[0x82], flags::END_HEADERS)).unwrap();
+    codec.process(&H2Codec::create_headers_frame(2, &[0x88], false)).unwrap();
+    let state = codec.stream_state(2).unwrap();
+    assert!(!state.reserved_remote);
+    assert!(state.headers_complete);
+}
+
+#[test]
+fn test_push_promise_block_decodes() {
+    let request = vec![
+        H2Header::new(":method", "GET"),
+        H2Header::new(":scheme", "https"),
+        H2Header::new(":authority", "example.com"),
+        H2Header::new(":path", "/style.css"),
+    ];
+    let block = HpackEncoder::new().encode(&request);
+    let frames = H2Codec::create_headers_frames(1, &block, false, 8);
+    // Reuse the HEADERS split, rewriting the first frame as a PUSH_PROMISE.
+    let first = &frames[0];
+    let mut all = push_promise(1, 2, &first[9..], 0);
+    for frame in &frames[1..] {
+        all.extend_from_slice(frame);
+    }
+    let events = codec().process(&all).unwrap();
+    match &events[..] {
+        [H2Event::PushPromise { header_block, .. }] => {
+            assert_eq!(HpackDecoder::new().decode(header_block).unwrap(), request);
+        }
+        other => panic!("Expected PushPromise, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_push_promise_errors() {
+    let cases: Vec<(Vec<u8>, &str)> = vec![
+        (push_promise(0, 2, &[0x82], flags::END_HEADERS), "stream 0"),
+        (push_promise(1, 3, &[0x82], flags::END_HEADERS), "invalid promised stream ID 3"),
+        (push_promise(1, 0, &[0x82], flags::END_HEADERS), "invalid promised stream ID 0"),
+        (build_frame(frame_type::PUSH_PROMISE, flags::END_HEADERS, 1, &[0, 0, 2]), "too short"),
+        (build_frame(frame_type::PUSH_PROMISE, flags::END_HEADERS | flags::PADDED, 1, &[9, 0, 0, 0, 2]), "padding"),
+    ];
+    for (frame, message) in cases {
+        let err = codec().process(&frame).unwrap_err();
+        assert!(err.contains(message), "{:?} does not mention {:?}", err, message);
+    }
+}
+
+#[test]
+fn test_promised_stream_must_be_idle() {
+    let mut codec = codec();
+    codec.process(&push_promise(1, 2, &[0x82], flags::END_HEADERS)).unwrap();
+    let err = codec.process(&push_promise(1, 2, &[0x82], flags::END_HEADERS)).unwrap_err();
+    assert!(err.contains("not idle"));
+}
+
+#[test]
+fn test_removed_stream_cannot_be_promised_again() {
+    let mut codec = codec();
+    codec.process(&push_promise(1, 4, &[0x82], flags::END_HEADERS)).unwrap();
+    codec.remove_stream(4);
+    for promised in [4, 2] {
+        let err = codec.process(&push_promise(1, promised, &[0x82], flags::END_HEADERS)).unwrap_err();
+        assert!(err.contains("PROTOCOL_ERROR"), "{}", err);
+    }
+
+    // Also after a restore
+    let mut restored = H2Codec::restore(&codec.snapshot()).unwrap();
+    assert!(restored.process(&push_promise(1, 4, &[0x82], flags::END_HEADERS)).is_err());
+    assert!(restored.process(&push_promise(1, 6, &[0x82], flags::END_HEADERS)).is_ok());
+}
+
+#[test]
+fn test_reserved_state_survives_snapshot() {
+    let mut codec = codec();
+    codec.process(&push_promise(1, 2, &[0x82], flags::END_HEADERS)).unwrap();
+    codec.process(&push_promise(1, 4, &[0x82], 0)).unwrap();
+    let mut restored = H2Codec::restore(&codec.snapshot()).unwrap();
+    assert!(restored.stream_state(2).unwrap().reserved_remote);
+    let events = restored.process(&H2Codec::create_continuation_frame(1, &[0x87], true)).unwrap();
+    assert!(matches!(events[0], H2Event::PushPromise { promised_stream_id: 4, .. }));
+}
@@ -226,7 +226,7 @@ fn test_continuation_then_data() {
     let events = c.process(&all_bytes).unwrap();
     assert_eq!(events.len(), 1);
     match &events[0] {
-        H2Event::Headers { stream_id, header_block, end_stream } => {
+        H2Event::Headers { stream_id, header_block, end_stream, .. } => {
             assert_eq!(*stream_id, 1);
             assert_eq!(header_block, &block);
             assert!(!*end_stream);
@@ -0,0 +1,170 @@
+//! Tests for H2Codec snapshot and restore
+
+use h2_sans_io::{settings_id, H2Codec, H2Event, HpackDecoder, Role, SnapshotError, CONNECTION_PREFACE};
+
+fn with_preface(codec: &mut H2Codec) {
+    codec.set_preface_received(true);
+}
+
+fn restored(codec: &H2Codec) -> H2Codec {
+    H2Codec::restore(&codec.snapshot()).unwrap()
+}
+
+#[test]
+fn test_fresh_codec_roundtrip() {
+    let codec = H2Codec::new();
+    let restored = restored(&codec);
+    assert!(!restored.preface_received());
+    assert_eq!(restored.stream_count(), 0);
+}
+
+#[test]
+fn test_preface_flag_preserved() {
+    let mut codec = H2Codec::new();
+    codec.process(CONNECTION_PREFACE).unwrap();
+    assert!(restored(&codec).preface_received());
+}
+
+#[test]
+fn test_resume_mid_preface() {
+    let mut codec = H2Codec::new();
+    codec.process(&CONNECTION_PREFACE[..10]).unwrap();
+    let mut codec = restored(&codec);
+    let mut rest = CONNECTION_PREFACE[10..].to_vec();
+    rest.extend_from_slice(&[0, 0, 0, 4, 0, 0, 0, 0, 0]); // SETTINGS
+    let events = codec.process(&rest).unwrap();
+    assert!(codec.preface_received());
+    assert_eq!(events, vec![H2Event::Settings { ack: false, settings: vec![] }]);
+}
+
+#[test]
+fn test_resume_mid_frame() {
+    let mut codec = H2Codec::new();
+    with_preface(&mut codec);
+    let frame = H2Codec::create_data_frames(1, b"hello world", true, 16384).remove(0);
+    assert!(codec.process(&frame[..12]).unwrap().is_empty());
+
+    let mut codec = restored(&codec);
+    let events = codec.process(&frame[12..]).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::Data { stream_id: 1, data: b"hello world".to_vec(), end_stream: true }]
+    );
+}
+
+#[test]
+fn test_resume_mid_header_block() {
+    let mut codec = H2Codec::new();
+    with_preface(&mut codec);
+    let block = [0x82, 0x86, 0x84, 0x41, 0x8a];
+    let frames = H2Codec::create_headers_frames(3, &block, true, 2);
+    assert_eq!(frames.len(), 3);
+    assert!(codec.process(&frames[0]).unwrap().is_empty());
+    assert!(codec.process(&frames[1]).unwrap().is_empty());
+
+    let mut codec = restored(&codec);
+    let events = codec.process(&frames[2]).unwrap();
+    assert_eq!(
+        events,
+        vec![H2Event::Headers { stream_id: 3, header_block: block.to_vec(), end_stream: true, priority: None }]
+    );
+}
+
+#[test]
+fn test_continuation_interlock_survives_restore() {
+    let mut codec = H2Codec::new();
+    with_preface(&mut codec);
+    let frames = H2Codec::create_headers_frames(1, &[0x82, 0x86], false, 1);
+    codec.process(&frames[0]).unwrap();
+
+    let mut codec = restored(&codec);
+    let ping = [0, 0, 8, 6, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8];
+    let err = codec.process(&ping).unwrap_err();
+    assert!(err.contains("CONTINUATION expected"), "{}", err);
+}
+
+#[test]
+fn test_stream_state_preserved() {
+    let mut codec = H2Codec::new();
+    with_preface(&mut codec);
+    for id in [1, 3, 5] {
+        codec.process(&H2Codec::create_headers_frame(id, &[0x82], false)).unwrap();
+    }
+    let restored = restored(&codec);
+    assert_eq!(restored.stream_count(), 3);
+    // Deterministic output regardless of HashMap iteration order
+    assert_eq!(restored.snapshot(), codec.snapshot());
+}
+
+#[test]
+fn test_resume_with_hpack_state() {
+    let mut encoder = h2_sans_io::HpackEncoder::new();
+    let mut decoder = HpackDecoder::new();
+    let mut codec = H2Codec::new();
+    with_preface(&mut codec);
+
+    let headers = vec![h2_sans_io::H2Header::new("x-session", "abc")];
+    let frame = H2Codec::create_headers_frame(1, &encoder.encode(&headers), true);
+    for event in codec.process(&frame).unwrap() {
+        if let H2Event::Headers { header_block, .. } = event {
+            decoder.decode(&header_block).unwrap();
+        }
+    }
+
+    // Checkpoint both the codec and the decoder, then resume
+    let mut codec = restored(&codec);
+    let mut decoder = HpackDecoder::restore(&decoder.snapshot()).unwrap();
+    let frame = H2Codec::create_headers_frame(3, &encoder.encode(&headers), true);
+    match &codec.process(&frame).unwrap()[0] {
+        H2Event::Headers { header_block, .. } => {
+            assert_eq!(header_block, &vec![0x80 | 62]);
+            assert_eq!(decoder.decode(header_block).unwrap(), headers);
+        }
+        other => panic!("Expected Headers event, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_restore_rejects_other_snapshot_kinds() {
+    let snap = HpackDecoder::new().snapshot();
+    assert_eq!(H2Codec::restore(&snap).unwrap_err(), SnapshotError::WrongKind);
+}
+
+#[test]
+fn test_restore_rejects_unknown_version() {
+    let mut snap = H2Codec::new().snapshot();
+    snap[4] = 0xFF;
+    assert_eq!(H2Codec::restore(&snap).unwrap_err(), SnapshotError::UnsupportedVersion(0xFF));
+}
+
+#[test]
+fn test_restore_rejects_truncated_snapshot() {
+    let mut codec = H2Codec::new();
+    with_preface(&mut codec);
+    codec.process(&H2Codec::create_headers_frames(1, &[0x82, 0x86], false, 1)[0]).unwrap();
+
+    // A server with every trailing field set
+    let mut server = H2Codec::new();
+    with_preface(&mut server);
+    server.set_role(Role::Server);
+    server.set_emit_unknown_frames(true);
+    server.process(&H2Codec::create_settings_frame(&[(settings_id::MAX_CONCURRENT_STREAMS, 4)])).unwrap();
+    server.process(&H2Codec::create_headers_frames(1, &[0x82], false, 16384)[0]).unwrap();
+    server.push_promise(1, &[0x82], 16384).unwrap();
+    server.push_promise(1, &[0x82], 16384).unwrap();
+    server.open_pushed_stream(2).unwrap();
+
+    for snap in [codec.snapshot(), server.snapshot()] {
+        for len in 0..snap.len() {
+            assert!(H2Codec::restore(&snap[..len]).is_err(), "prefix of {} bytes accepted", len);
+        }
+        assert!(H2Codec::restore(&snap).is_ok());
+    }
+}
+
+#[test]
+fn test_restore_rejects_trailing_bytes() {
+    let mut snap = H2Codec::new().snapshot();
+    snap.push(1);
+    assert!(matches!(H2Codec::restore(&snap), Err(SnapshotError::Invalid(_))));
+}
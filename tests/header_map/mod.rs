@@ -0,0 +1,3 @@
+//! Test module for HeaderMap
+
+mod operations;
@@ -0,0 +1,134 @@
+//! Tests for HeaderMap lookup, ordering and conversions
+
+use h2_sans_io::{H2Header, HeaderMap, HpackDecoder, HpackEncoder};
+
+fn h(name: &str, value: &str) -> H2Header {
+    H2Header::new(name, value)
+}
+
+fn values<'a>(map: &'a HeaderMap, name: &str) -> Vec<&'a [u8]> {
+    map.get_all(name).collect()
+}
+
+#[test]
+fn test_empty() {
+    let map = HeaderMap::new();
+    assert!(map.is_empty());
+    assert_eq!(map.len(), 0);
+    assert_eq!(map.get("x"), None);
+    assert!(map.into_headers().is_empty());
+}
+
+#[test]
+fn test_case_insensitive_lookup() {
+    let mut map = HeaderMap::new();
+    map.append("Content-Type", "text/html");
+    assert_eq!(map.get("content-type"), Some(&b"text/html"[..]));
+    assert_eq!(map.get("CONTENT-TYPE"), Some(&b"text/html"[..]));
+    assert!(map.contains(b"Content-type"));
+    // Names are stored as given
+    assert_eq!(map.iter().next().unwrap().name, b"Content-Type");
+}
+
+#[test]
+fn test_multiple_values() {
+    let mut map = HeaderMap::new();
+    map.append("accept", "text/html");
+    map.append("x-other", "1");
+    map.append("Accept", "*/*");
+    assert_eq!(values(&map, "accept"), vec![&b"text/html"[..], b"*/*"]);
+    assert_eq!(map.get("accept"), Some(&b"text/html"[..]));
+    assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn test_insert_replaces_in_place() {
+    let mut map = HeaderMap::from_headers(vec![h("a", "1"), h("b", "2"), h("a", "3"), h("c", "4")]);
+    map.insert("A", "x");
+    assert_eq!(map.into_headers(), vec![h("a", "x"), h("b", "2"), h("c", "4")]);
+
+    let mut map = HeaderMap::new();
+    map.insert("new", "v");
+    assert_eq!(map.get("new"), Some(&b"v"[..]));
+}
+
+#[test]
+fn test_remove() {
+    let mut map = HeaderMap::from_headers(vec![h("a", "1"), h("b", "2"), h("a", "3")]);
+    assert_eq!(map.remove("A"), vec![b"1".to_vec(), b"3".to_vec()]);
+    assert_eq!(map.get("a"), None);
+    assert_eq!(map.get("b"), Some(&b"2"[..]));
+    assert!(map.remove("missing").is_empty());
+    // Index stays consistent after removal
+    map.append("a", "5");
+    assert_eq!(values(&map, "a"), vec![&b"5"[..]]);
+    assert_eq!(map.into_headers(), vec![h("b", "2"), h("a", "5")]);
+}
+
+#[test]
+fn test_pseudo_headers_first_in_wire_order() {
+    let mut map = HeaderMap::new();
+    map.append("accept", "*/*");
+    map.append(":method", "GET");
+    map.append("user-agent", "test");
+    map.append(":path", "/");
+    assert_eq!(
+        map.clone().into_headers(),
+        vec![h(":method", "GET"), h(":path", "/"), h("accept", "*/*"), h("user-agent", "test")]
+    );
+    assert_eq!(map.pseudo_headers().count(), 2);
+    assert_eq!(map.regular_headers().count(), 2);
+    assert_eq!(map.get(":path"), Some(&b"/"[..]));
+}
+
+#[test]
+fn test_pseudo_insert_and_remove() {
+    let mut map = HeaderMap::from_headers(vec![h(":status", "200"), h("server", "x")]);
+    map.insert(":status", "404");
+    assert_eq!(map.get(":status"), Some(&b"404"[..]));
+    assert_eq!(map.remove(":status"), vec![b"404".to_vec()]);
+    assert_eq!(map.into_headers(), vec![h("server", "x")]);
+}
+
+#[test]
+fn test_conversions() {
+    let headers = vec![h(":status", "200"), h("set-cookie", "a=1"), h("set-cookie", "b=2")];
+    let map: HeaderMap = headers.clone().into();
+    let back: Vec<H2Header> = map.clone().into();
+    assert_eq!(back, headers);
+
+    let collected: HeaderMap = headers.clone().into_iter().collect();
+    assert_eq!(collected, map);
+    let iterated: Vec<H2Header> = map.clone().into_iter().collect();
+    assert_eq!(iterated, headers);
+    assert_eq!((&map).into_iter().count(), 3);
+
+    let mut extended = HeaderMap::new();
+    extended.extend(headers);
+    assert_eq!(extended, map);
+}
+
+#[test]
+fn test_hpack_roundtrip() {
+    let mut map = HeaderMap::new();
+    map.append("x-custom", "1");
+    map.append(":method", "GET");
+    map.append(":path", "/");
+    let block = HpackEncoder::new().encode(&map.clone().into_headers());
+    let decoded = HeaderMap::from_headers(HpackDecoder::new().decode(&block).unwrap());
+    assert_eq!(decoded, map);
+}
+
+#[test]
+fn test_uppercase_names_survive_roundtrip() {
+    use h2_sans_io::{validate_headers, ValidationProfile};
+
+    // Malformed in HTTP/2 (RFC 9113 Section 8.2.1); the map must not hide it
+    let decoded = vec![h(":METHOD", "GET"), h(":path", "/"), h(":scheme", "https"), h("X-Bad", "1")];
+    let map = HeaderMap::from_headers(decoded.clone());
+    assert_eq!(map.get(":method"), Some(&b"GET"[..]));
+    assert_eq!(map.get("x-bad"), Some(&b"1"[..]));
+    let headers = map.into_headers();
+    assert_eq!(headers, decoded);
+    assert!(validate_headers(&headers, ValidationProfile::Strict).is_err());
+}
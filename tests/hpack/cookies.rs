@@ -0,0 +1,97 @@
+//! Tests for cookie crumb splitting and joining (RFC 9113 Section 8.2.3)
+
+use h2_sans_io::{
+    join_cookies, split_cookies, DefaultIndexingPolicy, H2Header, HpackDecoder, HpackEncoder,
+    IndexingDecision, IndexingPolicy,
+};
+
+fn h(name: &str, value: &str) -> H2Header {
+    H2Header::new(name, value)
+}
+
+#[test]
+fn test_split_cookies() {
+    let headers = vec![h(":path", "/"), h("cookie", "a=b; c=d;e=f"), h("accept", "*/*")];
+    assert_eq!(
+        split_cookies(&headers),
+        vec![h(":path", "/"), h("cookie", "a=b"), h("cookie", "c=d"), h("cookie", "e=f"), h("accept", "*/*")]
+    );
+}
+
+#[test]
+fn test_split_drops_empty_crumbs() {
+    assert_eq!(split_cookies(&[h("cookie", " a=b ;; ; c=d ")]), vec![h("cookie", "a=b"), h("cookie", "c=d")]);
+    assert!(split_cookies(&[h("cookie", "")]).is_empty());
+}
+
+#[test]
+fn test_join_cookies() {
+    let headers = vec![h(":method", "GET"), h("cookie", "a=b"), h("accept", "*/*"), h("cookie", "c=d"), h("cookie", "e=f")];
+    assert_eq!(
+        join_cookies(headers),
+        vec![h(":method", "GET"), h("cookie", "a=b; c=d; e=f"), h("accept", "*/*")]
+    );
+}
+
+#[test]
+fn test_join_single_or_none_unchanged() {
+    let headers = vec![h("cookie", "a=b;c=d"), h("x", "y")];
+    assert_eq!(join_cookies(headers.clone()), headers);
+    assert_eq!(join_cookies(vec![h("x", "y")]), vec![h("x", "y")]);
+}
+
+#[test]
+fn test_split_then_join_roundtrip() {
+    let headers = vec![h("cookie", "session=abc; theme=dark; lang=en")];
+    assert_eq!(join_cookies(split_cookies(&headers)), headers);
+}
+
+#[test]
+fn test_encoder_splitting_off_by_default() {
+    let mut encoder = HpackEncoder::new();
+    assert!(!encoder.split_cookies());
+    let headers = vec![h("cookie", "a=b; c=d")];
+    let decoded = HpackDecoder::new().decode(&encoder.encode(&headers)).unwrap();
+    assert_eq!(decoded, headers);
+}
+
+#[test]
+fn test_encoder_splits_cookies() {
+    let mut encoder = HpackEncoder::new();
+    encoder.set_split_cookies(true);
+    let mut decoder = HpackDecoder::new();
+    let headers = vec![h(":method", "GET"), h("cookie", "a=b; c=d")];
+    let decoded = decoder.decode(&encoder.encode(&headers)).unwrap();
+    assert_eq!(decoded, vec![h(":method", "GET"), h("cookie", "a=b"), h("cookie", "c=d")]);
+    assert_eq!(join_cookies(decoded), headers);
+}
+
+#[test]
+fn test_split_crumbs_compress_with_indexing_policy() {
+    let mut encoder = HpackEncoder::new();
+    encoder.set_split_cookies(true);
+    encoder.set_indexing_policy(|h: &H2Header, m| {
+        if h.name == b"cookie" {
+            IndexingDecision::Index
+        } else {
+            DefaultIndexingPolicy.decide(h, m)
+        }
+    });
+    let mut decoder = HpackDecoder::new();
+
+    let first = vec![h("cookie", "session=0123456789abcdef; theme=dark")];
+    decoder.decode(&encoder.encode(&first)).unwrap();
+    // Only the changed crumb is sent as a literal; the other is one index byte
+    let second = vec![h("cookie", "session=0123456789abcdef; theme=light")];
+    let block = encoder.encode(&second);
+    assert_eq!(block[0], 0x80 | 63, "unchanged session crumb is indexed");
+    assert_eq!(join_cookies(decoder.decode(&block).unwrap()), second);
+}
+
+#[test]
+fn test_split_setting_survives_snapshot() {
+    let mut encoder = HpackEncoder::new();
+    encoder.set_split_cookies(true);
+    assert!(HpackEncoder::restore(&encoder.snapshot()).unwrap().split_cookies());
+    assert!(!HpackEncoder::restore(&HpackEncoder::new().snapshot()).unwrap().split_cookies());
+}
@@ -0,0 +1,129 @@
+//! Tests for typed HPACK decode errors and their reported offsets.
+
+use h2_sans_io::{error_code, HpackDecoder, HpackErrorKind};
+
+fn decode_err(block: &[u8]) -> h2_sans_io::HpackError {
+    HpackDecoder::new().decode(block).unwrap_err()
+}
+
+#[test]
+fn test_invalid_index() {
+    // :method GET, then index 70 with an empty dynamic table
+    let err = decode_err(&[0x82, 0x80 | 70]);
+    assert_eq!(err.kind, HpackErrorKind::InvalidIndex(70));
+    assert_eq!(err.offset, 1);
+}
+
+#[test]
+fn test_index_zero_is_invalid() {
+    let err = decode_err(&[0x80]);
+    assert_eq!(err.kind, HpackErrorKind::InvalidIndex(0));
+    assert_eq!(err.offset, 0);
+}
+
+#[test]
+fn test_invalid_literal_name_index() {
+    // Literal without indexing, name index 63 (dynamic, empty)
+    let err = decode_err(&[0x82, 0x0F, 48, 0x01, b'x']);
+    assert_eq!(err.kind, HpackErrorKind::InvalidIndex(63));
+    assert_eq!(err.offset, 1);
+}
+
+#[test]
+fn test_integer_overflow() {
+    let err = decode_err(&[0x82, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F]);
+    assert_eq!(err.kind, HpackErrorKind::IntegerOverflow);
+    assert_eq!(err.offset, 1);
+}
+
+#[test]
+fn test_truncated_integer() {
+    let err = decode_err(&[0x82, 0xFF, 0x80]);
+    assert_eq!(err.kind, HpackErrorKind::Truncated);
+    assert_eq!(err.offset, 1);
+}
+
+#[test]
+fn test_truncated_string() {
+    // New-name literal claiming a 10-byte name, only 3 present
+    let err = decode_err(&[0x82, 0x86, 0x40, 0x0A, b'a', b'b', b'c']);
+    assert_eq!(err.kind, HpackErrorKind::Truncated);
+    assert_eq!(err.offset, 2);
+}
+
+#[test]
+fn test_truncated_missing_value() {
+    let err = decode_err(&[0x40, 0x01, b'a']);
+    assert_eq!(err.kind, HpackErrorKind::Truncated);
+}
+
+#[test]
+fn test_invalid_huffman_padding() {
+    let err = decode_err(&[0x82, 0x40, 0x81, 0x00, 0x01, b'a']);
+    assert_eq!(err.kind, HpackErrorKind::InvalidHuffmanPadding);
+    assert_eq!(err.offset, 1);
+}
+
+#[test]
+fn test_huffman_padding_too_long() {
+    let err = decode_err(&[0x40, 0x82, 0x07, 0xFF, 0x01, b'a']);
+    assert_eq!(err.kind, HpackErrorKind::InvalidHuffmanPadding);
+}
+
+#[test]
+fn test_huffman_eos() {
+    let err = decode_err(&[0x40, 0x84, 0xFF, 0xFF, 0xFF, 0xFF, 0x01, b'a']);
+    assert_eq!(err.kind, HpackErrorKind::HuffmanEos);
+    assert_eq!(err.offset, 0);
+}
+
+#[test]
+fn test_size_update_after_field() {
+    let err = decode_err(&[0x82, 0x20, 0x86]);
+    assert_eq!(err.kind, HpackErrorKind::TableSizeUpdatePosition);
+    assert_eq!(err.offset, 1);
+}
+
+#[test]
+fn test_multiple_leading_size_updates_allowed() {
+    let mut decoder = HpackDecoder::new();
+    let headers = decoder.decode(&[0x20, 0x3F, 0xE1, 0x1F, 0x82]).unwrap();
+    assert_eq!(headers.len(), 1);
+    assert_eq!(decoder.max_dynamic_table_size(), 4096);
+}
+
+#[test]
+fn test_block_with_only_size_update_allowed() {
+    // An empty trailer block may carry nothing but a size update
+    let mut decoder = HpackDecoder::new();
+    assert!(decoder.decode(&[0x3F, 0x01]).unwrap().is_empty());
+    assert_eq!(decoder.max_dynamic_table_size(), 32);
+}
+
+#[test]
+fn test_size_update_above_default_limit() {
+    // 4097 = 31 + 4066 -> 0x3F, 0xE2, 0x1F
+    let err = decode_err(&[0x3F, 0xE2, 0x1F, 0x82]);
+    assert_eq!(err.kind, HpackErrorKind::LimitExceeded);
+    assert_eq!(err.offset, 0);
+}
+
+#[test]
+fn test_size_update_within_raised_limit() {
+    let mut decoder = HpackDecoder::new();
+    decoder.set_max_allowed_table_size(8192);
+    decoder.decode(&[0x3F, 0xE2, 0x1F, 0x82]).unwrap();
+    assert_eq!(decoder.max_dynamic_table_size(), 4097);
+}
+
+#[test]
+fn test_error_display_and_code() {
+    let err = decode_err(&[0x82, 0x80 | 70]);
+    let msg = err.to_string();
+    assert!(msg.starts_with("HPACK decode error"));
+    assert!(msg.contains("offset 1"));
+    assert!(msg.contains("70"));
+    assert_eq!(err.error_code(), error_code::COMPRESSION_ERROR);
+    let boxed: Box<dyn std::error::Error> = Box::new(err);
+    assert!(!boxed.to_string().is_empty());
+}
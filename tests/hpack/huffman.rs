@@ -0,0 +1,139 @@
+//! Tests for Huffman coding of string literals and HuffmanStrategy.
+//!
+//! Expected byte sequences come from RFC 7541 Appendix C.4.
+
+use h2_sans_io::{H2Header, HpackDecoder, HpackEncoder, HuffmanStrategy};
+
+/// "www.example.com" Huffman-coded (RFC 7541 C.4.1).
+const WWW_EXAMPLE_COM: [u8; 12] = [
+    0xf1, 0xe3, 0xc2, 0xe5, 0xf2, 0x3a, 0x6b, 0xa0, 0xab, 0x90, 0xf4, 0xff,
+];
+
+#[test]
+fn test_default_strategy_is_never() {
+    let encoder = HpackEncoder::new();
+    assert_eq!(encoder.huffman_strategy(), HuffmanStrategy::Never);
+}
+
+#[test]
+fn test_never_emits_raw_literal() {
+    let mut encoder = HpackEncoder::with_huffman_strategy(HuffmanStrategy::Never);
+    let encoded = encoder.encode(&[H2Header::new(":authority", "www.example.com")]);
+    // Indexed name (:authority = 1), raw 15-byte value
+    let mut expected = vec![0x01, 0x0f];
+    expected.extend_from_slice(b"www.example.com");
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_always_matches_rfc_vector() {
+    let mut encoder = HpackEncoder::with_huffman_strategy(HuffmanStrategy::Always);
+    let encoded = encoder.encode(&[H2Header::new(":authority", "www.example.com")]);
+    let mut expected = vec![0x01, 0x80 | 12];
+    expected.extend_from_slice(&WWW_EXAMPLE_COM);
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_always_encodes_custom_name_and_value() {
+    let mut encoder = HpackEncoder::with_huffman_strategy(HuffmanStrategy::Always);
+    let encoded = encoder.encode(&[H2Header::new("custom-key", "custom-value")]);
+    // RFC 7541 C.4.3: custom-key / custom-value
+    let expected = vec![
+        0x40, 0x88, 0x25, 0xa8, 0x49, 0xe9, 0x5b, 0xa9, 0x7d, 0x7f,
+        0x89, 0x25, 0xa8, 0x49, 0xe9, 0x5b, 0xb8, 0xe8, 0xb4, 0xbf,
+    ];
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_shorter_uses_huffman_for_text() {
+    let mut encoder = HpackEncoder::with_huffman_strategy(HuffmanStrategy::Shorter);
+    let encoded = encoder.encode(&[H2Header::new(":authority", "www.example.com")]);
+    assert_eq!(encoded[1], 0x80 | 12);
+}
+
+#[test]
+fn test_shorter_keeps_binary_raw() {
+    // High bytes have 20+ bit codes, so Huffman would be longer than raw.
+    let mut encoder = HpackEncoder::with_huffman_strategy(HuffmanStrategy::Shorter);
+    let value: Vec<u8> = (0x80..=0x8F).collect();
+    let encoded = encoder.encode(&[H2Header::new("x-bin", value.clone())]);
+    let mut decoder = HpackDecoder::new();
+    let fields = decoder.decode_fields(&encoded).unwrap();
+    assert!(fields[0].name_huffman, "short ASCII name should be Huffman-coded");
+    assert!(!fields[0].value_huffman, "binary value should stay raw");
+    assert_eq!(fields[0].header.value, value);
+}
+
+#[test]
+fn test_always_binary_roundtrip() {
+    let mut encoder = HpackEncoder::with_huffman_strategy(HuffmanStrategy::Always);
+    let mut decoder = HpackDecoder::new();
+    let value: Vec<u8> = (0..=255).collect();
+    let headers = vec![H2Header::new("x-all-bytes-bin", value)];
+    let encoded = encoder.encode(&headers);
+    assert_eq!(decoder.decode(&encoded).unwrap(), headers);
+}
+
+#[test]
+fn test_switch_strategy_mid_connection() {
+    let mut encoder = HpackEncoder::new();
+    let mut decoder = HpackDecoder::new();
+    let headers = vec![
+        H2Header::new("x-trace", "abc"),
+        H2Header::new("user-agent", "test/1.0"),
+    ];
+    for strategy in [HuffmanStrategy::Never, HuffmanStrategy::Always, HuffmanStrategy::Shorter] {
+        encoder.set_huffman_strategy(strategy);
+        let encoded = encoder.encode(&headers);
+        assert_eq!(decoder.decode(&encoded).unwrap(), headers);
+    }
+}
+
+#[test]
+fn test_decode_fields_reports_rfc_request() {
+    // RFC 7541 C.4.1: first request with Huffman coding
+    let mut block = vec![0x82, 0x86, 0x84, 0x41, 0x8c];
+    block.extend_from_slice(&WWW_EXAMPLE_COM);
+    let mut decoder = HpackDecoder::new();
+    let fields = decoder.decode_fields(&block).unwrap();
+    assert_eq!(fields.len(), 4);
+    assert!(fields[..3].iter().all(|f| !f.name_huffman && !f.value_huffman));
+    assert_eq!(fields[3].header, H2Header::new(":authority", "www.example.com"));
+    assert!(!fields[3].name_huffman);
+    assert!(fields[3].value_huffman);
+}
+
+#[test]
+fn test_decode_fields_raw_literal() {
+    let mut decoder = HpackDecoder::new();
+    let block = [0x40, 0x03, b'a', b'b', b'c', 0x01, b'x'];
+    let fields = decoder.decode_fields(&block).unwrap();
+    assert!(!fields[0].name_huffman);
+    assert!(!fields[0].value_huffman);
+}
+
+#[test]
+fn test_decode_rejects_zero_padding() {
+    // '0' is the 5-bit code 00000; the remaining 3 bits must be ones.
+    let mut decoder = HpackDecoder::new();
+    let block = [0x40, 0x81, 0x00, 0x01, b'a'];
+    assert!(decoder.decode(&block).is_err());
+}
+
+#[test]
+fn test_decode_rejects_padding_longer_than_seven_bits() {
+    // A full byte of ones is padding, not a symbol prefix that may be ignored.
+    let mut decoder = HpackDecoder::new();
+    let block = [0x40, 0x82, 0x07, 0xFF, 0x01, b'a'];
+    assert!(decoder.decode(&block).is_err());
+}
+
+#[test]
+fn test_decode_rejects_eos_symbol() {
+    // EOS is 30 ones; four 0xFF bytes contain it.
+    let mut decoder = HpackDecoder::new();
+    let block = [0x40, 0x84, 0xFF, 0xFF, 0xFF, 0xFF, 0x01, b'a'];
+    assert!(decoder.decode(&block).is_err());
+}
@@ -0,0 +1,173 @@
+//! Tests for fragment-by-fragment header block decoding.
+
+use h2_sans_io::{H2Header, HpackDecoder, HpackEncoder, HpackErrorKind, HuffmanStrategy};
+
+fn sample_headers() -> Vec<H2Header> {
+    vec![
+        H2Header::new(":method", "GET"),
+        H2Header::new(":path", "/index.html"),
+        H2Header::new(":authority", "www.example.com"),
+        H2Header::new("x-custom", "some longer custom value"),
+        H2Header::new("x-custom", "some longer custom value"),
+    ]
+}
+
+/// Feed `block` in chunks of `chunk` bytes, collecting all fields.
+fn decode_in_chunks(decoder: &mut HpackDecoder, block: &[u8], chunk: usize) -> Vec<H2Header> {
+    let chunks: Vec<&[u8]> = block.chunks(chunk).collect();
+    let mut out = Vec::new();
+    for (i, part) in chunks.iter().enumerate() {
+        out.extend(decoder.decode_fragment(part, i + 1 == chunks.len()).unwrap());
+    }
+    out
+}
+
+#[test]
+fn test_single_fragment_matches_decode() {
+    let block = HpackEncoder::new().encode(&sample_headers());
+    let mut decoder = HpackDecoder::new();
+    assert_eq!(decoder.decode_fragment(&block, true).unwrap(), sample_headers());
+}
+
+#[test]
+fn test_every_split_point() {
+    let mut encoder = HpackEncoder::with_huffman_strategy(HuffmanStrategy::Always);
+    let block = encoder.encode(&sample_headers());
+    for chunk in 1..=block.len() {
+        let mut decoder = HpackDecoder::new();
+        assert_eq!(decode_in_chunks(&mut decoder, &block, chunk), sample_headers(), "chunk {}", chunk);
+        assert_eq!(decoder.dynamic_table().len(), 1, "only x-custom is indexed");
+    }
+}
+
+#[test]
+fn test_fields_emitted_before_block_end() {
+    // :method GET, :path / (indexed), then half of a literal
+    let block = [0x82, 0x84, 0x40, 0x03, b'a', b'b'];
+    let mut decoder = HpackDecoder::new();
+    let fields = decoder.decode_fragment(&block, false).unwrap();
+    assert_eq!(fields, vec![H2Header::new(":method", "GET"), H2Header::new(":path", "/")]);
+    assert!(decoder.dynamic_table().is_empty());
+
+    let fields = decoder.decode_fragment(&[b'c', 0x01, b'x'], true).unwrap();
+    assert_eq!(fields, vec![H2Header::new("abc", "x")]);
+    assert_eq!(decoder.dynamic_table().len(), 1);
+}
+
+#[test]
+fn test_empty_fragments() {
+    let mut decoder = HpackDecoder::new();
+    assert!(decoder.decode_fragment(&[], false).unwrap().is_empty());
+    assert_eq!(decoder.decode_fragment(&[0x82], false).unwrap().len(), 1);
+    assert!(decoder.decode_fragment(&[], true).unwrap().is_empty());
+}
+
+#[test]
+fn test_truncated_at_block_end() {
+    let mut decoder = HpackDecoder::new();
+    decoder.decode_fragment(&[0x82, 0x40, 0x05, b'a'], false).unwrap();
+    let err = decoder.decode_fragment(b"b", true).unwrap_err();
+    assert_eq!(err.kind, HpackErrorKind::Truncated);
+    assert_eq!(err.offset, 1, "offset is relative to the whole block");
+}
+
+#[test]
+fn test_error_offset_spans_fragments() {
+    let mut decoder = HpackDecoder::new();
+    decoder.decode_fragment(&[0x82, 0x86], false).unwrap();
+    let err = decoder.decode_fragment(&[0x84, 0x80 | 70], false).unwrap_err();
+    assert_eq!(err.kind, HpackErrorKind::InvalidIndex(70));
+    assert_eq!(err.offset, 3);
+}
+
+#[test]
+fn test_state_resets_between_blocks() {
+    let mut decoder = HpackDecoder::new();
+    decoder.decode_fragment(&[0x82], true).unwrap();
+    // A size update is allowed at the start of the next block
+    let fields = decoder.decode_fragment(&[0x3F, 0xE1, 0x1F], false).unwrap();
+    assert!(fields.is_empty());
+    assert_eq!(decoder.decode_fragment(&[0x86], true).unwrap().len(), 1);
+
+    // After an error the next block starts clean as well
+    decoder.decode_fragment(&[0x40, 0x05], false).unwrap();
+    assert!(decoder.decode_fragment(&[0x80], true).is_err());
+    assert_eq!(decoder.decode_fragment(&[0x82], true).unwrap().len(), 1);
+}
+
+#[test]
+fn test_size_update_position_across_fragments() {
+    let mut decoder = HpackDecoder::new();
+    decoder.decode_fragment(&[0x82], false).unwrap();
+    let err = decoder.decode_fragment(&[0x20], false).unwrap_err();
+    assert_eq!(err.kind, HpackErrorKind::TableSizeUpdatePosition);
+    assert_eq!(err.offset, 1);
+}
+
+#[test]
+fn test_header_list_limit_fails_early() {
+    let mut decoder = HpackDecoder::new();
+    // :method GET is 7 + 3 + 32 = 42 octets
+    decoder.set_max_header_list_size(Some(84));
+    assert_eq!(decoder.decode_fragment(&[0x82, 0x82], false).unwrap().len(), 2);
+    let err = decoder.decode_fragment(&[0x82], false).unwrap_err();
+    assert_eq!(err.kind, HpackErrorKind::HeaderListTooLarge);
+    assert_eq!(err.offset, 2);
+    // The limit counts per block
+    assert_eq!(decoder.decode(&[0x82, 0x82]).unwrap().len(), 2);
+}
+
+#[test]
+fn test_declared_literal_length_checked_before_buffering() {
+    let mut decoder = HpackDecoder::new();
+    decoder.set_max_header_list_size(Some(1024));
+    // Literal without indexing, new name, declared length 2^32 - 1
+    let err = decoder.decode_fragment(&[0x00, 0x7F, 0x80, 0xFF, 0xFF, 0xFF, 0x0F], false).unwrap_err();
+    assert_eq!(err.kind, HpackErrorKind::HeaderListTooLarge);
+    assert_eq!(err.offset, 0);
+
+    // Value length checked after the name; Huffman lengths too
+    let mut block = vec![0x00, 0x01, b'a', 0xFF, 0xFF, 0xFF, 0x7F];
+    block.extend_from_slice(&[0xFF; 8]);
+    let err = decoder.decode_fragment(&block, false).unwrap_err();
+    assert_eq!(err.kind, HpackErrorKind::HeaderListTooLarge);
+
+    // A literal that fits is still buffered across fragments
+    let block = [0x00, 0x01, b'a', 0x03, b'x', b'y', b'z'];
+    assert!(decoder.decode_fragment(&block[..4], false).unwrap().is_empty());
+    assert_eq!(decoder.decode_fragment(&block[4..], true).unwrap(), vec![H2Header::new("a", "xyz")]);
+}
+
+#[test]
+fn test_header_list_limit_on_decode() {
+    let mut decoder = HpackDecoder::new();
+    decoder.set_max_header_list_size(Some(41));
+    assert_eq!(decoder.decode(&[0x82]).unwrap_err().kind, HpackErrorKind::HeaderListTooLarge);
+    decoder.set_max_header_list_size(None);
+    assert!(decoder.decode(&[0x82]).is_ok());
+}
+
+#[test]
+fn test_snapshot_mid_block() {
+    let block = HpackEncoder::new().encode(&sample_headers());
+    let (first, rest) = block.split_at(block.len() / 2);
+    let mut decoder = HpackDecoder::new();
+    decoder.set_max_header_list_size(Some(4096));
+    let mut fields = decoder.decode_fragment(first, false).unwrap();
+
+    let mut resumed = HpackDecoder::restore(&decoder.snapshot()).unwrap();
+    fields.extend(resumed.decode_fragment(rest, true).unwrap());
+    assert_eq!(fields, sample_headers());
+}
+
+#[test]
+fn test_decode_abandons_unfinished_fragment_block() {
+    let mut decoder = HpackDecoder::new();
+    // ":method: GET" and the first bytes of a literal split across fragments
+    assert_eq!(decoder.decode_fragment(&[0x82, 0x40, 0x0a, b'c'], false).unwrap(), vec![H2Header::new(":method", "GET")]);
+    assert_eq!(decoder.decode(&[0x82]).unwrap(), vec![H2Header::new(":method", "GET")]);
+
+    decoder.decode_fragment(&[0x82, 0x40], false).unwrap();
+    let fields = decoder.decode_fields(&[0x20, 0x84]).unwrap(); // size update first, then ":path: /"
+    assert_eq!(fields[0].header, H2Header::new(":path", "/"));
+}
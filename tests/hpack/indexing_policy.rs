@@ -0,0 +1,134 @@
+//! Tests for IndexingPolicy and its effect on HpackEncoder output.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use h2_sans_io::{
+    DefaultIndexingPolicy, FieldMatch, H2Header, HpackDecoder, HpackEncoder, IndexingDecision,
+    IndexingPolicy,
+};
+
+/// Index user-agent values, never index request IDs, defer to the default otherwise.
+struct ProxyPolicy;
+
+impl IndexingPolicy for ProxyPolicy {
+    fn decide(&mut self, header: &H2Header, table_match: FieldMatch) -> IndexingDecision {
+        match header.name.as_slice() {
+            b"user-agent" => IndexingDecision::Index,
+            b"x-request-id" => IndexingDecision::NoIndex,
+            _ => DefaultIndexingPolicy.decide(header, table_match),
+        }
+    }
+}
+
+#[test]
+fn test_default_policy_decisions() {
+    let h = H2Header::new("x", "y");
+    let mut policy = DefaultIndexingPolicy;
+    assert_eq!(policy.decide(&h, FieldMatch::None), IndexingDecision::Index);
+    assert_eq!(policy.decide(&h, FieldMatch::Name), IndexingDecision::NoIndex);
+    assert_eq!(policy.decide(&h, FieldMatch::Full), IndexingDecision::NoIndex);
+}
+
+#[test]
+fn test_default_policy_indexes_new_names() {
+    let mut encoder = HpackEncoder::new();
+    let headers = vec![H2Header::new("x-custom", "value")];
+    let first = encoder.encode(&headers);
+    assert_eq!(first[0], 0x40, "new name: literal with incremental indexing");
+    // Second time the field is found at dynamic index 62
+    assert_eq!(encoder.encode(&headers), vec![0x80 | 62]);
+}
+
+#[test]
+fn test_default_policy_does_not_index_known_names() {
+    let mut encoder = HpackEncoder::new();
+    let headers = vec![H2Header::new("user-agent", "curl/8.0")];
+    let first = encoder.encode(&headers);
+    let second = encoder.encode(&headers);
+    assert_eq!(first, second, "known name stays a literal without indexing");
+    // user-agent is static index 58: 4-bit prefix saturates at 15, then 43
+    assert_eq!(&first[..2], &[0x0F, 43]);
+}
+
+#[test]
+fn test_custom_policy_indexes_user_agent() {
+    let mut encoder = HpackEncoder::new();
+    encoder.set_indexing_policy(ProxyPolicy);
+    let mut decoder = HpackDecoder::new();
+    let headers = vec![H2Header::new("user-agent", "curl/8.0")];
+
+    let first = encoder.encode(&headers);
+    // Incremental indexing with indexed name: 6-bit prefix holds 58 directly
+    assert_eq!(first[0], 0x40 | 58);
+    let second = encoder.encode(&headers);
+    assert_eq!(second, vec![0x80 | 62]);
+
+    assert_eq!(decoder.decode(&first).unwrap(), headers);
+    assert_eq!(decoder.decode(&second).unwrap(), headers);
+}
+
+#[test]
+fn test_custom_policy_keeps_unique_ids_out_of_table() {
+    let mut encoder = HpackEncoder::new();
+    encoder.set_indexing_policy(ProxyPolicy);
+    let mut decoder = HpackDecoder::new();
+    for i in 0..3 {
+        let headers = vec![H2Header::new("x-request-id", format!("req-{}", i))];
+        let encoded = encoder.encode(&headers);
+        assert_eq!(encoded[0], 0x00, "literal without indexing, new name");
+        assert_eq!(decoder.decode(&encoded).unwrap(), headers);
+    }
+}
+
+#[test]
+fn test_never_index_overrides_full_match() {
+    let mut encoder = HpackEncoder::new();
+    let headers = vec![H2Header::new(":method", "GET")];
+    encoder.set_indexing_policy(|_: &H2Header, _| IndexingDecision::NeverIndex);
+    let encoded = encoder.encode(&headers);
+    // Never-indexed literal with indexed name :method (2)
+    assert_eq!(encoded, vec![0x10 | 2, 3, b'G', b'E', b'T']);
+    let mut decoder = HpackDecoder::new();
+    assert_eq!(decoder.decode(&encoded).unwrap(), headers);
+}
+
+#[test]
+fn test_never_index_sensitive_header_roundtrip() {
+    let mut encoder = HpackEncoder::new();
+    encoder.set_indexing_policy(|h: &H2Header, m| {
+        if h.name == b"authorization" {
+            IndexingDecision::NeverIndex
+        } else {
+            DefaultIndexingPolicy.decide(h, m)
+        }
+    });
+    let mut decoder = HpackDecoder::new();
+    let headers = vec![
+        H2Header::new("authorization", "Bearer secret"),
+        H2Header::new("x-trace", "1"),
+    ];
+    let encoded = encoder.encode(&headers);
+    assert_eq!(encoded[0], 0x10 | 15, "never-indexed, name = static index 23 (15 + 8)");
+    assert_eq!(decoder.decode(&encoded).unwrap(), headers);
+    // Still a literal on the second block
+    let encoded = encoder.encode(&headers[..1]);
+    assert_eq!(encoded[0] & 0xF0, 0x10);
+}
+
+#[test]
+fn test_policy_consulted_once_per_header() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let mut encoder = HpackEncoder::new();
+    encoder.set_indexing_policy(move |h: &H2Header, m| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        DefaultIndexingPolicy.decide(h, m)
+    });
+    encoder.encode(&[
+        H2Header::new(":method", "GET"),
+        H2Header::new(":path", "/"),
+        H2Header::new("x-a", "b"),
+    ]);
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+}
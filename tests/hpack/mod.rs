@@ -1,6 +1,15 @@
 //! Test module for hpack
 
 mod binary_and_state;
+mod cookies;
 mod decoding;
 mod edge_cases;
-mod encoding;
+mod encoding;
+mod errors;
+mod huffman;
+mod incremental;
+mod indexing_policy;
+mod rfc7541_examples;
+mod snapshot;
+mod stateless;
+mod table_introspection;
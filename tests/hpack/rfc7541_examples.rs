@@ -0,0 +1,181 @@
+//! RFC 7541 Appendix C examples, decoded with the dynamic table checked after
+//! every header block.
+
+use h2_sans_io::{H2Header, HpackDecoder};
+
+fn hex(s: &str) -> Vec<u8> {
+    let s: String = s.split_whitespace().collect();
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+fn headers(fields: &[(&str, &str)]) -> Vec<H2Header> {
+    fields.iter().map(|(n, v)| H2Header::new(*n, *v)).collect()
+}
+
+/// Decode `block` and compare the header list, the dynamic table (name,
+/// value, size in index order) and the table size.
+fn check(decoder: &mut HpackDecoder, block: &str, expected: &[(&str, &str)], table: &[(&str, &str, usize)], size: usize) {
+    assert_eq!(decoder.decode(&hex(block)).unwrap(), headers(expected));
+    let actual: Vec<(Vec<u8>, Vec<u8>, usize)> =
+        decoder.dynamic_table().iter().map(|e| (e.name.to_vec(), e.value.to_vec(), e.size)).collect();
+    let table: Vec<(Vec<u8>, Vec<u8>, usize)> =
+        table.iter().map(|(n, v, s)| (n.as_bytes().to_vec(), v.as_bytes().to_vec(), *s)).collect();
+    assert_eq!(actual, table);
+    assert_eq!(decoder.dynamic_table_size(), size);
+}
+
+/// Dynamic table size update to 256, the SETTINGS_HEADER_TABLE_SIZE the
+/// response examples (C.5, C.6) assume.
+const SIZE_256: &str = "3fe101";
+
+#[test]
+fn test_c2_1_literal_with_indexing() {
+    let mut decoder = HpackDecoder::new();
+    check(
+        &mut decoder,
+        "400a 6375 7374 6f6d 2d6b 6579 0d63 7573 746f 6d2d 6865 6164 6572",
+        &[("custom-key", "custom-header")],
+        &[("custom-key", "custom-header", 55)],
+        55,
+    );
+}
+
+#[test]
+fn test_c2_2_literal_without_indexing() {
+    let mut decoder = HpackDecoder::new();
+    check(&mut decoder, "040c 2f73 616d 706c 652f 7061 7468", &[(":path", "/sample/path")], &[], 0);
+}
+
+#[test]
+fn test_c2_3_literal_never_indexed() {
+    let mut decoder = HpackDecoder::new();
+    check(&mut decoder, "1008 7061 7373 776f 7264 0673 6563 7265 74", &[("password", "secret")], &[], 0);
+}
+
+#[test]
+fn test_c2_4_indexed_field() {
+    let mut decoder = HpackDecoder::new();
+    check(&mut decoder, "82", &[(":method", "GET")], &[], 0);
+}
+
+fn requests(blocks: [&str; 3]) {
+    let mut decoder = HpackDecoder::new();
+    check(
+        &mut decoder,
+        blocks[0],
+        &[(":method", "GET"), (":scheme", "http"), (":path", "/"), (":authority", "www.example.com")],
+        &[(":authority", "www.example.com", 57)],
+        57,
+    );
+    check(
+        &mut decoder,
+        blocks[1],
+        &[
+            (":method", "GET"),
+            (":scheme", "http"),
+            (":path", "/"),
+            (":authority", "www.example.com"),
+            ("cache-control", "no-cache"),
+        ],
+        &[("cache-control", "no-cache", 53), (":authority", "www.example.com", 57)],
+        110,
+    );
+    check(
+        &mut decoder,
+        blocks[2],
+        &[
+            (":method", "GET"),
+            (":scheme", "https"),
+            (":path", "/index.html"),
+            (":authority", "www.example.com"),
+            ("custom-key", "custom-value"),
+        ],
+        &[("custom-key", "custom-value", 54), ("cache-control", "no-cache", 53), (":authority", "www.example.com", 57)],
+        164,
+    );
+}
+
+#[test]
+fn test_c3_requests_without_huffman() {
+    requests([
+        "8286 8441 0f77 7777 2e65 7861 6d70 6c65 2e63 6f6d",
+        "8286 84be 5808 6e6f 2d63 6163 6865",
+        "8287 85bf 400a 6375 7374 6f6d 2d6b 6579 0c63 7573 746f 6d2d 7661 6c75 65",
+    ]);
+}
+
+#[test]
+fn test_c4_requests_with_huffman() {
+    requests([
+        "8286 8441 8cf1 e3c2 e5f2 3a6b a0ab 90f4 ff",
+        "8286 84be 5886 a8eb 1064 9cbf",
+        "8287 85bf 4088 25a8 49e9 5ba9 7d7f 8925 a849 e95b b8e8 b4bf",
+    ]);
+}
+
+const DATE_21: &str = "Mon, 21 Oct 2013 20:13:21 GMT";
+const DATE_22: &str = "Mon, 21 Oct 2013 20:13:22 GMT";
+const LOCATION: &str = "https://www.example.com";
+const COOKIE: &str = "foo=ASDJKHQKBZXOQWEOPIUAXQWEOIU; max-age=3600; version=1";
+
+fn responses(blocks: [&str; 3]) {
+    let mut decoder = HpackDecoder::new();
+    decoder.set_max_allowed_table_size(256);
+    check(
+        &mut decoder,
+        &format!("{}{}", SIZE_256, blocks[0]),
+        &[(":status", "302"), ("cache-control", "private"), ("date", DATE_21), ("location", LOCATION)],
+        &[("location", LOCATION, 63), ("date", DATE_21, 65), ("cache-control", "private", 52), (":status", "302", 42)],
+        222,
+    );
+    assert_eq!(decoder.max_dynamic_table_size(), 256);
+    // ":status: 307" evicts ":status: 302"
+    check(
+        &mut decoder,
+        blocks[1],
+        &[(":status", "307"), ("cache-control", "private"), ("date", DATE_21), ("location", LOCATION)],
+        &[(":status", "307", 42), ("location", LOCATION, 63), ("date", DATE_21, 65), ("cache-control", "private", 52)],
+        222,
+    );
+    // The new date, content-encoding and set-cookie entries evict the rest
+    check(
+        &mut decoder,
+        blocks[2],
+        &[
+            (":status", "200"),
+            ("cache-control", "private"),
+            ("date", DATE_22),
+            ("location", LOCATION),
+            ("content-encoding", "gzip"),
+            ("set-cookie", COOKIE),
+        ],
+        &[("set-cookie", COOKIE, 98), ("content-encoding", "gzip", 52), ("date", DATE_22, 65)],
+        215,
+    );
+}
+
+#[test]
+fn test_c5_responses_without_huffman() {
+    responses([
+        "4803 3330 3258 0770 7269 7661 7465 611d 4d6f 6e2c 2032 3120 4f63 7420 3230 3133
+         2032 303a 3133 3a32 3120 474d 546e 1768 7474 7073 3a2f 2f77 7777 2e65 7861 6d70
+         6c65 2e63 6f6d",
+        "4803 3330 37c1 c0bf",
+        "88c1 611d 4d6f 6e2c 2032 3120 4f63 7420 3230 3133 2032 303a 3133 3a32 3220 474d
+         54c0 5a04 677a 6970 7738 666f 6f3d 4153 444a 4b48 514b 425a 584f 5157 454f 5049
+         5541 5851 5745 4f49 553b 206d 6178 2d61 6765 3d33 3630 303b 2076 6572 7369 6f6e
+         3d31",
+    ]);
+}
+
+#[test]
+fn test_c6_responses_with_huffman() {
+    responses([
+        "4882 6402 5885 aec3 771a 4b61 96d0 7abe 9410 54d4 44a8 2005 9504 0b81 66e0 82a6
+         2d1b ff6e 919d 29ad 1718 63c7 8f0b 97c8 e9ae 82ae 43d3",
+        "4883 640e ffc1 c0bf",
+        "88c1 6196 d07a be94 1054 d444 a820 0595 040b 8166 e084 a62d 1bff c05a 839b d9ab
+         77ad 94e7 821d d7f2 e6c7 b335 dfdf cd5b 3960 d5af 2708 7f36 72c1 ab27 0fb5 291f
+         9587 3160 65c0 03ed 4ee5 b106 3d50 07",
+    ]);
+}
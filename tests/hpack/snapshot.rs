@@ -0,0 +1,129 @@
+//! Tests for HpackEncoder/HpackDecoder snapshot and restore.
+
+use h2_sans_io::{H2Header, HpackDecoder, HpackEncoder, HuffmanStrategy, SnapshotError};
+
+fn request(i: usize) -> Vec<H2Header> {
+    vec![
+        H2Header::new(":method", "GET"),
+        H2Header::new(":path", format!("/item/{}", i)),
+        H2Header::new("x-session", "s-42"),
+        H2Header::new(format!("x-field-{}", i % 3), "v"),
+    ]
+}
+
+fn table_dump(entries: &[h2_sans_io::TableEntry<'_>]) -> Vec<(usize, Vec<u8>, Vec<u8>)> {
+    entries.iter().map(|e| (e.index, e.name.to_vec(), e.value.to_vec())).collect()
+}
+
+#[test]
+fn test_decoder_roundtrip_preserves_table() {
+    let mut encoder = HpackEncoder::new();
+    let mut decoder = HpackDecoder::new();
+    for i in 0..5 {
+        decoder.decode(&encoder.encode(&request(i))).unwrap();
+    }
+    let restored = HpackDecoder::restore(&decoder.snapshot()).unwrap();
+    assert_eq!(table_dump(&restored.dynamic_table()), table_dump(&decoder.dynamic_table()));
+    assert_eq!(restored.dynamic_table_size(), decoder.dynamic_table_size());
+    assert_eq!(restored.max_dynamic_table_size(), decoder.max_dynamic_table_size());
+}
+
+#[test]
+fn test_resumed_connection_keeps_decoding() {
+    let mut encoder = HpackEncoder::new();
+    let mut decoder = HpackDecoder::new();
+    for i in 0..3 {
+        decoder.decode(&encoder.encode(&request(i))).unwrap();
+    }
+
+    // Suspend both halves and resume them in a "new worker"
+    let mut encoder = HpackEncoder::restore(&encoder.snapshot()).unwrap();
+    let mut decoder = HpackDecoder::restore(&decoder.snapshot()).unwrap();
+
+    for i in 3..8 {
+        let encoded = encoder.encode(&request(i));
+        assert_eq!(decoder.decode(&encoded).unwrap(), request(i));
+    }
+}
+
+#[test]
+fn test_restored_encoder_reuses_dynamic_indices() {
+    let mut encoder = HpackEncoder::new();
+    let headers = vec![H2Header::new("x-custom", "value")];
+    encoder.encode(&headers);
+    let mut restored = HpackEncoder::restore(&encoder.snapshot()).unwrap();
+    assert_eq!(restored.encode(&headers), vec![0x80 | 62]);
+}
+
+#[test]
+fn test_encoder_snapshot_keeps_huffman_strategy() {
+    let encoder = HpackEncoder::with_huffman_strategy(HuffmanStrategy::Shorter);
+    let restored = HpackEncoder::restore(&encoder.snapshot()).unwrap();
+    assert_eq!(restored.huffman_strategy(), HuffmanStrategy::Shorter);
+}
+
+#[test]
+fn test_decoder_snapshot_keeps_size_limits() {
+    let mut decoder = HpackDecoder::new();
+    decoder.set_max_allowed_table_size(8192);
+    // Size update to 6000, then :method GET
+    decoder.decode(&[0x3F, 0xD1, 0x2E, 0x82]).unwrap();
+    assert_eq!(decoder.max_dynamic_table_size(), 6000);
+
+    let mut restored = HpackDecoder::restore(&decoder.snapshot()).unwrap();
+    assert_eq!(restored.max_dynamic_table_size(), 6000);
+    // The raised limit survives: 8192 is still accepted
+    restored.decode(&[0x3F, 0xE1, 0x3F, 0x82]).unwrap();
+    assert_eq!(restored.max_dynamic_table_size(), 8192);
+}
+
+#[test]
+fn test_empty_state_roundtrip() {
+    let restored = HpackDecoder::restore(&HpackDecoder::new().snapshot()).unwrap();
+    assert!(restored.dynamic_table().is_empty());
+    let restored = HpackEncoder::restore(&HpackEncoder::new().snapshot()).unwrap();
+    assert!(restored.dynamic_table().is_empty());
+}
+
+#[test]
+fn test_restore_rejects_wrong_kind() {
+    let enc = HpackEncoder::new().snapshot();
+    assert_eq!(HpackDecoder::restore(&enc).unwrap_err(), SnapshotError::WrongKind);
+    let dec = HpackDecoder::new().snapshot();
+    assert_eq!(HpackEncoder::restore(&dec).unwrap_err(), SnapshotError::WrongKind);
+}
+
+#[test]
+fn test_restore_rejects_unknown_version() {
+    let mut snap = HpackDecoder::new().snapshot();
+    snap[4] = 99;
+    assert_eq!(HpackDecoder::restore(&snap).unwrap_err(), SnapshotError::UnsupportedVersion(99));
+}
+
+#[test]
+fn test_restore_rejects_truncation() {
+    let mut encoder = HpackEncoder::new();
+    let mut decoder = HpackDecoder::new();
+    decoder.decode(&encoder.encode(&request(0))).unwrap();
+    let snap = decoder.snapshot();
+    for len in 0..snap.len() {
+        assert!(HpackDecoder::restore(&snap[..len]).is_err(), "prefix of {} bytes accepted", len);
+    }
+}
+
+#[test]
+fn test_restore_rejects_trailing_bytes() {
+    let mut snap = HpackEncoder::new().snapshot();
+    snap.push(0);
+    assert!(matches!(HpackEncoder::restore(&snap), Err(SnapshotError::Invalid(_))));
+}
+
+#[test]
+fn test_restore_rejects_oversized_table() {
+    let mut encoder = HpackEncoder::new();
+    encoder.encode(&[H2Header::new("x-a", "b")]);
+    let mut snap = encoder.snapshot();
+    // Layout: magic(4) version(1) huffman(1) max_size(8) ...; shrink max_size to 1
+    snap[6..14].copy_from_slice(&1u64.to_be_bytes());
+    assert!(matches!(HpackEncoder::restore(&snap), Err(SnapshotError::Invalid(_))));
+}
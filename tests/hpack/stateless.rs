@@ -0,0 +1,84 @@
+//! Tests for encode_stateless (static table and literals only).
+
+use h2_sans_io::{encode_stateless, H2Header, HpackDecoder, HpackEncoder, HuffmanStrategy};
+
+fn error_response() -> Vec<H2Header> {
+    vec![
+        H2Header::new(":status", "502"),
+        H2Header::new("content-type", "text/plain"),
+        H2Header::new("x-proxy-error", "upstream unreachable"),
+    ]
+}
+
+#[test]
+fn test_static_full_match_is_indexed() {
+    let encoded = encode_stateless(&[H2Header::new(":status", "200")], HuffmanStrategy::Never);
+    assert_eq!(encoded, vec![0x88]);
+}
+
+#[test]
+fn test_static_name_match_is_literal_without_indexing() {
+    let encoded = encode_stateless(&[H2Header::new(":status", "502")], HuffmanStrategy::Never);
+    assert_eq!(encoded, vec![0x08, 3, b'5', b'0', b'2']);
+}
+
+#[test]
+fn test_new_name_is_literal_without_indexing() {
+    let encoded = encode_stateless(&[H2Header::new("x-a", "b")], HuffmanStrategy::Never);
+    assert_eq!(encoded, vec![0x00, 3, b'x', b'-', b'a', 1, b'b']);
+}
+
+#[test]
+fn test_repeated_header_never_indexed_dynamically() {
+    let headers = vec![H2Header::new("x-a", "b"); 3];
+    let encoded = encode_stateless(&headers, HuffmanStrategy::Never);
+    assert_eq!(encoded.len(), 3 * 7);
+}
+
+#[test]
+fn test_does_not_disturb_connection_tables() {
+    let mut encoder = HpackEncoder::new();
+    let mut decoder = HpackDecoder::new();
+
+    // Populate both dynamic tables through the connection's encoder
+    let request = vec![H2Header::new("x-session", "abc"), H2Header::new("x-user", "u1")];
+    decoder.decode(&encoder.encode(&request)).unwrap();
+    let before: Vec<_> = decoder.dynamic_table().iter().map(|e| e.index).collect();
+    let size_before = decoder.dynamic_table_size();
+
+    // A fallback component injects a stateless block mid-connection
+    let fallback = encode_stateless(&error_response(), HuffmanStrategy::Shorter);
+    assert_eq!(decoder.decode(&fallback).unwrap(), error_response());
+    let after: Vec<_> = decoder.dynamic_table().iter().map(|e| e.index).collect();
+    assert_eq!(before, after);
+    assert_eq!(size_before, decoder.dynamic_table_size());
+
+    // The connection encoder keeps working against the same table state
+    let encoded = encoder.encode(&request);
+    assert_eq!(decoder.decode(&encoded).unwrap(), request);
+}
+
+#[test]
+fn test_decodes_on_fresh_decoder() {
+    for strategy in [HuffmanStrategy::Never, HuffmanStrategy::Always, HuffmanStrategy::Shorter] {
+        let encoded = encode_stateless(&error_response(), strategy);
+        let mut decoder = HpackDecoder::new();
+        assert_eq!(decoder.decode(&encoded).unwrap(), error_response());
+        assert!(decoder.dynamic_table().is_empty());
+    }
+}
+
+#[test]
+fn test_huffman_strategy_applied() {
+    let raw = encode_stateless(&error_response(), HuffmanStrategy::Never);
+    let huff = encode_stateless(&error_response(), HuffmanStrategy::Shorter);
+    assert!(huff.len() < raw.len());
+    let mut decoder = HpackDecoder::new();
+    let fields = decoder.decode_fields(&huff).unwrap();
+    assert!(fields[2].name_huffman && fields[2].value_huffman);
+}
+
+#[test]
+fn test_empty_header_list() {
+    assert!(encode_stateless(&[], HuffmanStrategy::Never).is_empty());
+}
@@ -0,0 +1,127 @@
+//! Tests for the read-only dynamic table accessors.
+//!
+//! Header blocks and expected table states come from RFC 7541 Appendix C.3.
+
+use h2_sans_io::{H2Header, HpackDecoder, HpackEncoder};
+
+/// RFC 7541 C.3.1 - C.3.3: three requests without Huffman coding.
+const REQUEST_1: &[u8] = &[
+    0x82, 0x86, 0x84, 0x41, 0x0f, 0x77, 0x77, 0x77, 0x2e, 0x65, 0x78, 0x61, 0x6d, 0x70, 0x6c,
+    0x65, 0x2e, 0x63, 0x6f, 0x6d,
+];
+const REQUEST_2: &[u8] = &[
+    0x82, 0x86, 0x84, 0xbe, 0x58, 0x08, 0x6e, 0x6f, 0x2d, 0x63, 0x61, 0x63, 0x68, 0x65,
+];
+const REQUEST_3: &[u8] = &[
+    0x82, 0x87, 0x85, 0xbf, 0x40, 0x0a, 0x63, 0x75, 0x73, 0x74, 0x6f, 0x6d, 0x2d, 0x6b, 0x65,
+    0x79, 0x0c, 0x63, 0x75, 0x73, 0x74, 0x6f, 0x6d, 0x2d, 0x76, 0x61, 0x6c, 0x75, 0x65,
+];
+
+fn dump(entries: &[h2_sans_io::TableEntry<'_>]) -> Vec<(usize, Vec<u8>, Vec<u8>, usize)> {
+    entries
+        .iter()
+        .map(|e| (e.index, e.name.to_vec(), e.value.to_vec(), e.size))
+        .collect()
+}
+
+#[test]
+fn test_new_tables_are_empty() {
+    let decoder = HpackDecoder::new();
+    assert!(decoder.dynamic_table().is_empty());
+    assert_eq!(decoder.dynamic_table_size(), 0);
+    assert_eq!(decoder.max_dynamic_table_size(), 4096);
+
+    let encoder = HpackEncoder::new();
+    assert!(encoder.dynamic_table().is_empty());
+    assert_eq!(encoder.dynamic_table_size(), 0);
+    assert_eq!(encoder.max_dynamic_table_size(), 4096);
+}
+
+#[test]
+fn test_decoder_table_after_rfc_requests() {
+    let mut decoder = HpackDecoder::new();
+
+    decoder.decode(REQUEST_1).unwrap();
+    assert_eq!(
+        dump(&decoder.dynamic_table()),
+        vec![(62, b":authority".to_vec(), b"www.example.com".to_vec(), 57)]
+    );
+    assert_eq!(decoder.dynamic_table_size(), 57);
+
+    decoder.decode(REQUEST_2).unwrap();
+    assert_eq!(
+        dump(&decoder.dynamic_table()),
+        vec![
+            (62, b"cache-control".to_vec(), b"no-cache".to_vec(), 53),
+            (63, b":authority".to_vec(), b"www.example.com".to_vec(), 57),
+        ]
+    );
+    assert_eq!(decoder.dynamic_table_size(), 110);
+
+    decoder.decode(REQUEST_3).unwrap();
+    let entries = decoder.dynamic_table();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].index, 62);
+    assert_eq!(entries[0].name, b"custom-key");
+    assert_eq!(entries[0].value, b"custom-value");
+    assert_eq!(entries[0].size, 54);
+    assert_eq!(entries[2].name, b":authority");
+    assert_eq!(decoder.dynamic_table_size(), 164);
+}
+
+#[test]
+fn test_encoder_and_decoder_tables_agree() {
+    let mut encoder = HpackEncoder::new();
+    let mut decoder = HpackDecoder::new();
+    let blocks = [
+        vec![H2Header::new("x-a", "1"), H2Header::new("x-b", "2")],
+        vec![H2Header::new("x-c", "3"), H2Header::new("x-a", "1")],
+    ];
+    for headers in &blocks {
+        let encoded = encoder.encode(headers);
+        decoder.decode(&encoded).unwrap();
+        assert_eq!(dump(&encoder.dynamic_table()), dump(&decoder.dynamic_table()));
+        assert_eq!(encoder.dynamic_table_size(), decoder.dynamic_table_size());
+    }
+    assert_eq!(encoder.dynamic_table().len(), 3);
+}
+
+#[test]
+fn test_entry_size_includes_overhead() {
+    let mut encoder = HpackEncoder::new();
+    encoder.encode(&[H2Header::new("ab", "cde")]);
+    let entries = encoder.dynamic_table();
+    assert_eq!(entries[0].size, 2 + 3 + 32);
+    assert_eq!(encoder.dynamic_table_size(), 37);
+}
+
+#[test]
+fn test_size_update_reported_and_evicts() {
+    let mut decoder = HpackDecoder::new();
+    decoder.decode(REQUEST_1).unwrap();
+    assert_eq!(decoder.dynamic_table().len(), 1);
+
+    // Size update to 0 followed by :method GET
+    decoder.decode(&[0x20, 0x82]).unwrap();
+    assert_eq!(decoder.max_dynamic_table_size(), 0);
+    assert_eq!(decoder.dynamic_table_size(), 0);
+    assert!(decoder.dynamic_table().is_empty());
+}
+
+#[test]
+fn test_eviction_keeps_size_within_max() {
+    let mut encoder = HpackEncoder::new();
+    for i in 0..100 {
+        encoder.encode(&[H2Header::new(format!("x-header-{}", i), vec![b'v'; 100])]);
+        assert!(encoder.dynamic_table_size() <= encoder.max_dynamic_table_size());
+    }
+    let entries = encoder.dynamic_table();
+    // Newest entry is always index 62
+    assert_eq!(entries[0].name, b"x-header-99");
+    let total: usize = entries.iter().map(|e| e.size).sum();
+    assert_eq!(total, encoder.dynamic_table_size());
+    // Indices are contiguous
+    for (i, e) in entries.iter().enumerate() {
+        assert_eq!(e.index, 62 + i);
+    }
+}
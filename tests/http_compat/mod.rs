@@ -0,0 +1,3 @@
+//! Test module for the `http` crate conversions (feature `http`)
+
+mod parts;
@@ -0,0 +1,183 @@
+//! Tests for conversions between header lists and http::{request, response}::Parts
+
+use h2_sans_io::{
+    request_parts_from_headers, request_parts_to_headers, response_parts_from_headers,
+    response_parts_to_headers, validate_headers, H2Header, HeaderErrorKind, HpackDecoder, HpackEncoder,
+    HttpConversionError, Protocol, ValidationProfile,
+};
+use http::uri::Scheme;
+use http::{Method, StatusCode, Version};
+
+fn h(name: &str, value: &str) -> H2Header {
+    H2Header::new(name, value)
+}
+
+#[test]
+fn test_request_from_headers() {
+    let parts = request_parts_from_headers(vec![
+        h(":method", "POST"),
+        h(":scheme", "https"),
+        h(":authority", "example.com:8443"),
+        h(":path", "/api?x=1"),
+        h("content-type", "application/json"),
+        h("accept", "a"),
+        h("accept", "b"),
+    ])
+    .unwrap();
+    assert_eq!(parts.method, Method::POST);
+    assert_eq!(parts.uri.to_string(), "https://example.com:8443/api?x=1");
+    assert_eq!(parts.version, Version::HTTP_2);
+    assert_eq!(parts.headers["content-type"], "application/json");
+    assert_eq!(parts.headers.get_all("accept").iter().count(), 2);
+}
+
+#[test]
+fn test_request_without_authority_is_origin_form() {
+    let parts = request_parts_from_headers(vec![h(":method", "GET"), h(":scheme", "https"), h(":path", "/x")]).unwrap();
+    assert_eq!(parts.uri.to_string(), "/x");
+    assert_eq!(parts.extensions.get::<Scheme>(), Some(&Scheme::HTTPS));
+}
+
+#[test]
+fn test_origin_form_keeps_scheme() {
+    let headers = vec![h(":method", "GET"), h(":scheme", "http"), h(":path", "/x")];
+    let parts = request_parts_from_headers(headers.clone()).unwrap();
+    assert_eq!(parts.extensions.get::<Scheme>(), Some(&Scheme::HTTP));
+    assert_eq!(request_parts_to_headers(&parts), headers);
+}
+
+#[test]
+fn test_connect_request() {
+    let headers = vec![h(":method", "CONNECT"), h(":authority", "proxy.example:443")];
+    let parts = request_parts_from_headers(headers.clone()).unwrap();
+    assert_eq!(parts.method, Method::CONNECT);
+    assert_eq!(parts.uri.authority().unwrap().as_str(), "proxy.example:443");
+    assert_eq!(request_parts_to_headers(&parts), headers);
+}
+
+#[test]
+fn test_extended_connect_protocol_extension() {
+    let headers = vec![
+        h(":method", "CONNECT"),
+        h(":scheme", "https"),
+        h(":authority", "example.com"),
+        h(":path", "/chat"),
+        h(":protocol", "websocket"),
+        h("sec-websocket-version", "13"),
+    ];
+    let parts = request_parts_from_headers(headers.clone()).unwrap();
+    assert_eq!(parts.extensions.get::<Protocol>(), Some(&Protocol("websocket".to_string())));
+    assert_eq!(request_parts_to_headers(&parts), headers);
+}
+
+#[test]
+fn test_request_errors() {
+    let err = request_parts_from_headers(vec![h(":method", "GET")]).unwrap_err();
+    assert!(matches!(err, HttpConversionError::Malformed(e) if e.kind == HeaderErrorKind::MissingPseudo(":scheme")));
+
+    let err = request_parts_from_headers(vec![h(":method", "G ET"), h(":scheme", "https"), h(":path", "/")]).unwrap_err();
+    assert_eq!(err, HttpConversionError::InvalidMethod);
+
+    let err = request_parts_from_headers(vec![h(":method", "GET"), h(":scheme", "https"), h(":authority", "a b"), h(":path", "/")]).unwrap_err();
+    assert_eq!(err, HttpConversionError::InvalidUri);
+
+    let err = request_parts_from_headers(vec![h(":method", "GET"), h(":scheme", "https"), h(":path", "/"), h("ok", "1"), h("bad", "a\nb")]).unwrap_err();
+    assert_eq!(err, HttpConversionError::InvalidHeader(1));
+    assert_eq!(err.to_string(), "invalid header field 1");
+}
+
+#[test]
+fn test_request_to_headers() {
+    let (parts, ()) = http::Request::builder()
+        .method("PUT")
+        .uri("https://example.com/upload?id=7")
+        .header("content-length", "3")
+        .body(())
+        .unwrap()
+        .into_parts();
+    assert_eq!(
+        request_parts_to_headers(&parts),
+        vec![
+            h(":method", "PUT"),
+            h(":scheme", "https"),
+            h(":authority", "example.com"),
+            h(":path", "/upload?id=7"),
+            h("content-length", "3"),
+        ]
+    );
+}
+
+#[test]
+fn test_request_to_headers_drops_connection_specific_fields() {
+    let (parts, ()) = http::Request::builder()
+        .uri("https://example.com/")
+        .header("connection", "keep-alive")
+        .header("keep-alive", "timeout=5")
+        .header("proxy-connection", "keep-alive")
+        .header("transfer-encoding", "chunked")
+        .header("upgrade", "h2c")
+        .header("te", "gzip")
+        .header("host", "other.example")
+        .header("accept", "*/*")
+        .body(())
+        .unwrap()
+        .into_parts();
+    let headers = request_parts_to_headers(&parts);
+    assert_eq!(
+        headers,
+        vec![
+            h(":method", "GET"),
+            h(":scheme", "https"),
+            h(":authority", "example.com"),
+            h(":path", "/"),
+            h("accept", "*/*"),
+        ]
+    );
+    assert!(validate_headers(&headers, ValidationProfile::Strict).is_ok());
+
+    let (parts, ()) = http::Response::builder().header("connection", "close").header("te", "trailers").body(()).unwrap().into_parts();
+    assert_eq!(response_parts_to_headers(&parts), vec![h(":status", "200"), h("te", "trailers")]);
+}
+
+#[test]
+fn test_host_becomes_authority() {
+    let (parts, ()) = http::Request::builder().uri("/index.html").header("host", "example.com").body(()).unwrap().into_parts();
+    assert_eq!(
+        request_parts_to_headers(&parts),
+        vec![h(":method", "GET"), h(":scheme", "https"), h(":authority", "example.com"), h(":path", "/index.html")]
+    );
+}
+
+#[test]
+fn test_request_to_headers_defaults() {
+    let (parts, ()) = http::Request::builder().method("OPTIONS").uri("*").body(()).unwrap().into_parts();
+    let headers = request_parts_to_headers(&parts);
+    assert_eq!(headers, vec![h(":method", "OPTIONS"), h(":scheme", "https"), h(":path", "*")]);
+}
+
+#[test]
+fn test_request_roundtrip_through_hpack() {
+    let (parts, ()) = http::Request::builder().uri("http://localhost:8080/").body(()).unwrap().into_parts();
+    let block = HpackEncoder::new().encode(&request_parts_to_headers(&parts));
+    let decoded = request_parts_from_headers(HpackDecoder::new().decode(&block).unwrap()).unwrap();
+    assert_eq!(decoded.method, parts.method);
+    assert_eq!(decoded.uri, parts.uri);
+}
+
+#[test]
+fn test_response_conversions() {
+    let headers = vec![h(":status", "404"), h("content-type", "text/plain"), h("content-length", "9")];
+    let parts = response_parts_from_headers(headers.clone()).unwrap();
+    assert_eq!(parts.status, StatusCode::NOT_FOUND);
+    assert_eq!(parts.version, Version::HTTP_2);
+    assert_eq!(parts.headers["content-length"], "9");
+    assert_eq!(response_parts_to_headers(&parts), headers);
+}
+
+#[test]
+fn test_response_errors() {
+    let err = response_parts_from_headers(vec![h(":status", "2000")]).unwrap_err();
+    assert!(matches!(err, HttpConversionError::Malformed(e) if e.kind == HeaderErrorKind::InvalidPseudoValue));
+    let err = response_parts_from_headers(vec![h(":status", "200"), h("x", "\0")]).unwrap_err();
+    assert_eq!(err, HttpConversionError::InvalidHeader(0));
+}
@@ -0,0 +1,63 @@
+//! Tests for RFC 9218 priority field parsing (ExtensiblePriority)
+
+use h2_sans_io::ExtensiblePriority;
+
+fn parse(value: &str) -> Option<ExtensiblePriority> {
+    ExtensiblePriority::parse(value.as_bytes())
+}
+
+fn prio(urgency: u8, incremental: bool) -> ExtensiblePriority {
+    ExtensiblePriority { urgency, incremental }
+}
+
+#[test]
+fn test_defaults() {
+    assert_eq!(ExtensiblePriority::default(), prio(3, false));
+    assert_eq!(parse(""), Some(prio(3, false)));
+    assert_eq!(parse("   "), Some(prio(3, false)));
+}
+
+#[test]
+fn test_urgency_and_incremental() {
+    assert_eq!(parse("u=0"), Some(prio(0, false)));
+    assert_eq!(parse("u=7, i"), Some(prio(7, true)));
+    assert_eq!(parse("i, u=1"), Some(prio(1, true)));
+    assert_eq!(parse("i=?1"), Some(prio(3, true)));
+    assert_eq!(parse("u=2,i=?0"), Some(prio(2, false)));
+}
+
+#[test]
+fn test_last_duplicate_wins() {
+    assert_eq!(parse("u=1, u=5"), Some(prio(5, false)));
+}
+
+#[test]
+fn test_out_of_range_or_mistyped_values_ignored() {
+    assert_eq!(parse("u=8"), Some(prio(3, false)));
+    assert_eq!(parse("u=-1"), Some(prio(3, false)));
+    assert_eq!(parse("u=1.5"), Some(prio(3, false)));
+    assert_eq!(parse("u=\"1\""), Some(prio(3, false)));
+    assert_eq!(parse("i=1"), Some(prio(3, false)));
+    assert_eq!(parse("u"), Some(prio(3, false)));
+}
+
+#[test]
+fn test_unknown_members_and_parameters_ignored() {
+    assert_eq!(parse("u=2;foo=bar, x=(a \"b\" ?1);y, i;p=:aGk=:, z=tok/en:1"), Some(prio(2, true)));
+}
+
+#[test]
+fn test_invalid_dictionary() {
+    for value in ["u=1,", "U=1", "u=1 i", "u=?2", "u=\"open", ",u=1", "u=1;;", "u=(1"] {
+        assert_eq!(parse(value), None, "{:?}", value);
+    }
+}
+
+#[test]
+fn test_serialize() {
+    assert_eq!(prio(3, false).to_field_value(), "");
+    assert_eq!(prio(0, false).to_field_value(), "u=0");
+    assert_eq!(prio(3, true).to_string(), "i");
+    assert_eq!(prio(5, true).to_string(), "u=5, i");
+    assert_eq!(parse(&prio(6, true).to_string()), Some(prio(6, true)));
+}
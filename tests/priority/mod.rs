@@ -0,0 +1,6 @@
+//! Test module for stream prioritization (RFC 7540 tree, RFC 9218 urgency)
+
+mod extensible;
+mod scheduler;
+mod tree;
+mod urgency;
@@ -0,0 +1,141 @@
+//! Tests for PriorityTree::next_stream weighted-fair ordering
+
+use h2_sans_io::{PriorityTree, StreamPriority};
+
+fn prio(dependency: u32, weight: u16) -> StreamPriority {
+    StreamPriority { exclusive: false, dependency, weight: (weight - 1) as u8 }
+}
+
+fn picks(tree: &mut PriorityTree, n: usize) -> Vec<u32> {
+    (0..n).map(|_| tree.next_stream().unwrap()).collect()
+}
+
+fn count(picks: &[u32], id: u32) -> usize {
+    picks.iter().filter(|&&p| p == id).count()
+}
+
+#[test]
+fn test_nothing_ready() {
+    let mut tree = PriorityTree::new();
+    assert_eq!(tree.next_stream(), None);
+    tree.open(1, None);
+    assert_eq!(tree.next_stream(), None);
+}
+
+#[test]
+fn test_weighted_share() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, Some(prio(0, 192)));
+    tree.open(3, Some(prio(0, 64)));
+    tree.set_ready(1, true);
+    tree.set_ready(3, true);
+    let order = picks(&mut tree, 400);
+    assert_eq!(count(&order, 1), 300);
+    assert_eq!(count(&order, 3), 100);
+}
+
+#[test]
+fn test_equal_weights_alternate() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, None);
+    tree.open(3, None);
+    tree.set_ready(1, true);
+    tree.set_ready(3, true);
+    assert_eq!(picks(&mut tree, 4), vec![1, 3, 1, 3]);
+}
+
+#[test]
+fn test_parent_served_before_dependents() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, None);
+    tree.open(3, Some(prio(1, 256)));
+    tree.set_ready(1, true);
+    tree.set_ready(3, true);
+    assert_eq!(picks(&mut tree, 3), vec![1, 1, 1]);
+    tree.set_ready(1, false);
+    assert_eq!(tree.next_stream(), Some(3));
+}
+
+#[test]
+fn test_closed_parent_passes_share_to_dependents() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, Some(prio(0, 1)));
+    tree.open(3, Some(prio(0, 1)));
+    tree.open(5, Some(prio(1, 16)));
+    tree.open(7, Some(prio(1, 16)));
+    tree.close(1);
+    for id in [3, 5, 7] {
+        tree.set_ready(id, true);
+    }
+    let order = picks(&mut tree, 40);
+    // Stream 1's branch and stream 3 split evenly; 5 and 7 split the branch.
+    assert_eq!(count(&order, 3), 20);
+    assert_eq!(count(&order, 5), 10);
+    assert_eq!(count(&order, 7), 10);
+}
+
+#[test]
+fn test_newly_ready_stream_does_not_burst() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, None);
+    tree.open(3, None);
+    tree.set_ready(1, true);
+    picks(&mut tree, 50);
+    tree.set_ready(3, true);
+    assert_eq!(picks(&mut tree, 4), vec![3, 1, 3, 1]);
+}
+
+#[test]
+fn test_closed_stream_not_scheduled() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, None);
+    tree.set_ready(1, true);
+    tree.close(1);
+    assert!(!tree.is_ready(1));
+    tree.set_ready(1, true);
+    assert_eq!(tree.next_stream(), None);
+}
+
+#[test]
+fn test_deep_chain_without_recursion() {
+    // Run on a small stack: picks must not recurse per tree level.
+    std::thread::Builder::new()
+        .stack_size(256 * 1024)
+        .spawn(|| {
+            let mut tree = PriorityTree::new();
+            tree.open(1, None);
+            let depth = 20_000;
+            for i in 1..depth {
+                tree.open(2 * i + 1, Some(prio(2 * i - 1, 16)));
+            }
+            let deepest = 2 * depth - 1;
+            tree.set_ready(deepest, true);
+            assert_eq!(picks(&mut tree, 3), vec![deepest; 3]);
+            tree.set_ready(1, true);
+            assert_eq!(tree.next_stream(), Some(1));
+            tree.close(1);
+            assert_eq!(tree.next_stream(), Some(deepest));
+            tree.set_ready(deepest, false);
+            assert_eq!(tree.next_stream(), None);
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn test_ready_counts_follow_moves() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, None);
+    tree.open(3, Some(prio(1, 16)));
+    tree.open(5, None);
+    tree.set_ready(3, true);
+    // Move 1 (with ready 3 below it) under 5, then remove 5
+    tree.reprioritize(1, prio(5, 16));
+    assert_eq!(tree.next_stream(), Some(3));
+    tree.remove(5);
+    tree.remove(1);
+    assert_eq!(tree.next_stream(), Some(3));
+    tree.remove(3);
+    assert_eq!(tree.next_stream(), None);
+}
@@ -0,0 +1,189 @@
+//! Tests for PriorityTree dependency graph maintenance
+
+use h2_sans_io::{PriorityTree, StreamPriority, DEFAULT_MAX_IDLE_STREAMS, DEFAULT_WEIGHT};
+
+fn prio(dependency: u32, weight: u16, exclusive: bool) -> StreamPriority {
+    StreamPriority { exclusive, dependency, weight: (weight - 1) as u8 }
+}
+
+fn weight(tree: &PriorityTree, id: u32) -> u16 {
+    tree.priority(id).unwrap().effective_weight()
+}
+
+fn parent(tree: &PriorityTree, id: u32) -> u32 {
+    tree.priority(id).unwrap().dependency
+}
+
+#[test]
+fn test_default_priority() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, None);
+    assert_eq!(parent(&tree, 1), 0);
+    assert_eq!(weight(&tree, 1), DEFAULT_WEIGHT);
+    assert_eq!(tree.children(0), &[1]);
+    assert_eq!(tree.len(), 1);
+}
+
+#[test]
+fn test_open_with_priority() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, None);
+    tree.open(3, Some(prio(1, 200, false)));
+    assert_eq!(parent(&tree, 3), 1);
+    assert_eq!(weight(&tree, 3), 200);
+}
+
+#[test]
+fn test_dependency_on_unknown_stream_gets_default() {
+    let mut tree = PriorityTree::new();
+    tree.open(3, Some(prio(99, 200, true)));
+    assert_eq!(parent(&tree, 3), 0);
+    assert_eq!(weight(&tree, 3), DEFAULT_WEIGHT);
+}
+
+#[test]
+fn test_self_dependency_ignored() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, None);
+    tree.reprioritize(1, prio(1, 100, false));
+    assert_eq!(weight(&tree, 1), DEFAULT_WEIGHT);
+}
+
+#[test]
+fn test_priority_frame_for_idle_stream() {
+    let mut tree = PriorityTree::new();
+    tree.reprioritize(5, prio(0, 32, false));
+    tree.open(7, Some(prio(5, 16, false)));
+    // HEADERS without priority keeps the place set by the PRIORITY frame.
+    tree.open(5, None);
+    assert_eq!(weight(&tree, 5), 32);
+    assert_eq!(tree.children(5), &[7]);
+}
+
+#[test]
+fn test_exclusive_insertion() {
+    // RFC 7540 Section 5.3.1 example: D exclusive on A adopts B and C.
+    let mut tree = PriorityTree::new();
+    tree.open(1, None);
+    tree.open(3, Some(prio(1, 16, false)));
+    tree.open(5, Some(prio(1, 16, false)));
+    tree.open(7, Some(prio(1, 16, true)));
+    assert_eq!(tree.children(1), &[7]);
+    assert_eq!(tree.children(7), &[3, 5]);
+}
+
+#[test]
+fn test_move_under_own_dependent() {
+    // RFC 7540 Section 5.3.3 example: A moved exclusively under D.
+    //     x                x
+    //     |                |
+    //     A                D
+    //    / \               |
+    //   B   C     ==>      A
+    //      / \            /|\
+    //     D   E          B C F
+    //     |                |
+    //     F                E
+    let (x, a, b, c, d, e, f) = (1, 3, 5, 7, 9, 11, 13);
+    let mut tree = PriorityTree::new();
+    tree.open(x, None);
+    tree.open(a, Some(prio(x, 16, false)));
+    tree.open(b, Some(prio(a, 16, false)));
+    tree.open(c, Some(prio(a, 16, false)));
+    tree.open(d, Some(prio(c, 16, false)));
+    tree.open(e, Some(prio(c, 16, false)));
+    tree.open(f, Some(prio(d, 16, false)));
+
+    tree.reprioritize(a, prio(d, 16, true));
+    assert_eq!(tree.children(x), &[d]);
+    assert_eq!(tree.children(d), &[a]);
+    let mut under_a = tree.children(a).to_vec();
+    under_a.sort();
+    assert_eq!(under_a, vec![b, c, f]);
+    assert_eq!(tree.children(c), &[e]);
+}
+
+#[test]
+fn test_remove_redistributes_weight() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, Some(prio(0, 64, false)));
+    tree.open(3, Some(prio(1, 10, false)));
+    tree.open(5, Some(prio(1, 30, false)));
+    tree.remove(1);
+    assert!(!tree.contains(1));
+    assert_eq!(parent(&tree, 3), 0);
+    assert_eq!(weight(&tree, 3), 16);
+    assert_eq!(weight(&tree, 5), 48);
+}
+
+#[test]
+fn test_remove_never_drops_weight_below_one() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, Some(prio(0, 1, false)));
+    tree.open(3, Some(prio(1, 1, false)));
+    tree.open(5, Some(prio(1, 256, false)));
+    tree.remove(1);
+    assert_eq!(weight(&tree, 3), 1);
+    assert_eq!(weight(&tree, 5), 1);
+}
+
+#[test]
+fn test_closed_stream_retained_until_evicted() {
+    let mut tree = PriorityTree::new();
+    tree.set_max_closed_streams(2);
+    tree.open(1, None);
+    tree.open(3, Some(prio(1, 16, false)));
+    tree.close(1);
+    assert!(tree.is_closed(1));
+    // A new stream can still depend on the closed stream.
+    tree.open(5, Some(prio(1, 16, false)));
+    assert_eq!(parent(&tree, 5), 1);
+
+    tree.open(7, None);
+    tree.open(9, None);
+    tree.close(7);
+    tree.close(9);
+    assert!(!tree.contains(1));
+    assert!(tree.contains(7) && tree.contains(9));
+    assert_eq!(parent(&tree, 3), 0);
+}
+
+#[test]
+fn test_zero_retention_removes_on_close() {
+    let mut tree = PriorityTree::new();
+    tree.set_max_closed_streams(0);
+    tree.open(1, None);
+    tree.close(1);
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn test_idle_streams_capped() {
+    let mut tree = PriorityTree::new();
+    tree.open(1, None);
+    // A peer sending PRIORITY for ever more idle streams
+    for id in (3..20_000).step_by(2) {
+        tree.reprioritize(id, prio(id - 2, 16, false));
+    }
+    assert_eq!(tree.len(), 1 + DEFAULT_MAX_IDLE_STREAMS);
+    assert!(tree.contains(1));
+    assert!(tree.contains(19_999));
+    assert!(!tree.contains(3));
+}
+
+#[test]
+fn test_opened_stream_no_longer_idle() {
+    let mut tree = PriorityTree::new();
+    tree.set_max_idle_streams(1);
+    tree.reprioritize(3, prio(0, 32, false));
+    tree.open(3, None);
+    tree.reprioritize(5, prio(3, 16, false));
+    tree.reprioritize(7, prio(3, 16, false));
+    assert!(tree.contains(3));
+    assert!(!tree.contains(5));
+    assert_eq!(tree.children(3), &[7]);
+    assert_eq!(weight(&tree, 3), 32);
+
+    tree.set_max_idle_streams(0);
+    assert_eq!(tree.len(), 1);
+}
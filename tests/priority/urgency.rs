@@ -0,0 +1,122 @@
+//! Tests for UrgencyScheduler ordering
+
+use h2_sans_io::{ExtensiblePriority, UrgencyScheduler, DEFAULT_MAX_UNOPENED_STREAMS};
+
+fn prio(urgency: u8, incremental: bool) -> Option<ExtensiblePriority> {
+    Some(ExtensiblePriority { urgency, incremental })
+}
+
+fn picks(scheduler: &mut UrgencyScheduler, n: usize) -> Vec<u32> {
+    (0..n).map(|_| scheduler.next_stream().unwrap()).collect()
+}
+
+#[test]
+fn test_nothing_ready() {
+    let mut scheduler = UrgencyScheduler::new();
+    assert_eq!(scheduler.next_stream(), None);
+    scheduler.open(1, None);
+    assert_eq!(scheduler.next_stream(), None);
+}
+
+#[test]
+fn test_lower_urgency_value_first() {
+    let mut scheduler = UrgencyScheduler::new();
+    scheduler.open(1, prio(6, false));
+    scheduler.open(3, None);
+    scheduler.open(5, prio(0, true));
+    for id in [1, 3, 5] {
+        scheduler.set_ready(id, true);
+    }
+    assert_eq!(scheduler.next_stream(), Some(5));
+    scheduler.set_ready(5, false);
+    assert_eq!(scheduler.next_stream(), Some(3));
+    scheduler.set_ready(3, false);
+    assert_eq!(scheduler.next_stream(), Some(1));
+}
+
+#[test]
+fn test_non_incremental_served_one_at_a_time_in_id_order() {
+    let mut scheduler = UrgencyScheduler::new();
+    scheduler.open(5, None);
+    scheduler.open(3, None);
+    scheduler.set_ready(5, true);
+    scheduler.set_ready(3, true);
+    assert_eq!(picks(&mut scheduler, 3), vec![3, 3, 3]);
+    scheduler.remove(3);
+    assert_eq!(scheduler.next_stream(), Some(5));
+}
+
+#[test]
+fn test_incremental_round_robin() {
+    let mut scheduler = UrgencyScheduler::new();
+    for id in [1, 3, 5] {
+        scheduler.open(id, prio(3, true));
+        scheduler.set_ready(id, true);
+    }
+    assert_eq!(picks(&mut scheduler, 6), vec![1, 3, 5, 1, 3, 5]);
+}
+
+#[test]
+fn test_non_incremental_before_incremental_at_same_urgency() {
+    let mut scheduler = UrgencyScheduler::new();
+    scheduler.open(1, prio(3, true));
+    scheduler.open(3, prio(3, false));
+    scheduler.set_ready(1, true);
+    scheduler.set_ready(3, true);
+    assert_eq!(picks(&mut scheduler, 2), vec![3, 3]);
+}
+
+#[test]
+fn test_priority_update_overrides_header() {
+    let mut scheduler = UrgencyScheduler::new();
+    scheduler.update(7, ExtensiblePriority { urgency: 1, incremental: false });
+    scheduler.open(7, prio(5, true));
+    assert_eq!(scheduler.priority(7), prio(1, false));
+
+    scheduler.open(9, prio(5, false));
+    scheduler.update(9, ExtensiblePriority { urgency: 0, incremental: true });
+    assert_eq!(scheduler.priority(9), prio(0, true));
+}
+
+#[test]
+fn test_remove() {
+    let mut scheduler = UrgencyScheduler::new();
+    scheduler.open(1, None);
+    scheduler.set_ready(1, true);
+    scheduler.remove(1);
+    assert!(scheduler.is_empty());
+    assert!(!scheduler.is_ready(1));
+    assert_eq!(scheduler.next_stream(), None);
+}
+
+#[test]
+fn test_unopened_streams_capped() {
+    let mut scheduler = UrgencyScheduler::new();
+    assert_eq!(scheduler.max_unopened_streams(), DEFAULT_MAX_UNOPENED_STREAMS);
+    scheduler.open(1, None);
+    for id in (3..1003).step_by(2) {
+        scheduler.update(id, ExtensiblePriority { urgency: 0, incremental: false });
+    }
+    assert_eq!(scheduler.len(), 1 + DEFAULT_MAX_UNOPENED_STREAMS);
+    // Oldest updates were dropped; open streams are never evicted
+    assert!(scheduler.contains(1));
+    assert!(!scheduler.contains(3));
+    assert!(scheduler.contains(1001));
+
+    scheduler.set_max_unopened_streams(2);
+    assert_eq!(scheduler.len(), 3);
+    assert!(scheduler.contains(999) && scheduler.contains(1001));
+}
+
+#[test]
+fn test_opened_stream_no_longer_unopened() {
+    let mut scheduler = UrgencyScheduler::new();
+    scheduler.set_max_unopened_streams(1);
+    scheduler.update(1, ExtensiblePriority { urgency: 0, incremental: false });
+    scheduler.open(1, None);
+    scheduler.update(3, ExtensiblePriority { urgency: 0, incremental: false });
+    scheduler.update(5, ExtensiblePriority { urgency: 0, incremental: false });
+    assert!(scheduler.contains(1));
+    assert!(!scheduler.contains(3));
+    assert_eq!(scheduler.priority(1), prio(0, false));
+}
@@ -0,0 +1,154 @@
+//! Tests for content-length checks against received DATA
+
+use h2_sans_io::{error_code, H2Header, H2Request, Role, StreamErrorKind, StreamEvent, StreamTracker};
+
+fn request_with_length(length: &str) -> Vec<H2Header> {
+    vec![
+        H2Header::new(":method", "POST"),
+        H2Header::new(":scheme", "https"),
+        H2Header::new(":path", "/"),
+        H2Header::new("content-length", length),
+    ]
+}
+
+fn response(status: &str, length: &str) -> Vec<H2Header> {
+    vec![H2Header::new(":status", status), H2Header::new("content-length", length)]
+}
+
+fn error_kind(event: StreamEvent) -> StreamErrorKind {
+    match event {
+        StreamEvent::StreamError { error, .. } => {
+            assert_eq!(error.error_code(), error_code::PROTOCOL_ERROR);
+            error.kind
+        }
+        other => panic!("Expected StreamError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_exact_length_accepted() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    tracker.recv_headers(1, request_with_length("10"), false);
+    assert!(matches!(tracker.recv_data(1, vec![0; 4], false), StreamEvent::Data { .. }));
+    assert!(matches!(tracker.recv_data(1, vec![0; 6], false), StreamEvent::Data { .. }));
+    assert!(matches!(tracker.recv_data(1, vec![], true), StreamEvent::Data { end_stream: true, .. }));
+}
+
+#[test]
+fn test_overshoot_reported_immediately() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    tracker.recv_headers(1, request_with_length("5"), false);
+    tracker.recv_data(1, vec![0; 3], false);
+    let kind = error_kind(tracker.recv_data(1, vec![0; 3], false));
+    assert_eq!(kind, StreamErrorKind::ContentLengthMismatch { declared: 5, received: 6 });
+}
+
+#[test]
+fn test_shortfall_reported_at_end_stream() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    tracker.recv_headers(1, request_with_length("5"), false);
+    assert!(matches!(tracker.recv_data(1, vec![0; 3], false), StreamEvent::Data { .. }));
+    let kind = error_kind(tracker.recv_data(1, vec![0; 1], true));
+    assert_eq!(kind, StreamErrorKind::ContentLengthMismatch { declared: 5, received: 4 });
+}
+
+#[test]
+fn test_shortfall_at_trailers() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    tracker.recv_headers(1, request_with_length("5"), false);
+    tracker.recv_data(1, vec![0; 2], false);
+    let kind = error_kind(tracker.recv_headers(1, vec![H2Header::new("x-checksum", "0")], true));
+    assert_eq!(kind, StreamErrorKind::ContentLengthMismatch { declared: 5, received: 2 });
+}
+
+#[test]
+fn test_head_with_end_stream_and_nonzero_length() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    let kind = error_kind(tracker.recv_headers(1, request_with_length("3"), true));
+    assert_eq!(kind, StreamErrorKind::ContentLengthMismatch { declared: 3, received: 0 });
+    assert!(matches!(tracker.recv_headers(3, request_with_length("0"), true), StreamEvent::Request { .. }));
+}
+
+#[test]
+fn test_no_content_length_not_checked() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.recv_headers(1, vec![H2Header::new(":status", "200")], false);
+    assert!(matches!(tracker.recv_data(1, vec![0; 1000], true), StreamEvent::Data { .. }));
+}
+
+#[test]
+fn test_invalid_content_length() {
+    for bad in ["", "abc", "-1", "1.5", "+5", "5, 6", "99999999999999999999999"] {
+        let mut tracker = StreamTracker::new(Role::Client);
+        assert_eq!(error_kind(tracker.recv_headers(1, response("200", bad), false)), StreamErrorKind::InvalidContentLength, "{:?}", bad);
+    }
+}
+
+#[test]
+fn test_repeated_identical_values_accepted() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    assert!(matches!(tracker.recv_headers(1, response("200", "3, 3"), false), StreamEvent::Response { .. }));
+    let mut headers = response("200", "3");
+    headers.push(H2Header::new("content-length", "3"));
+    assert!(matches!(tracker.recv_headers(3, headers, false), StreamEvent::Response { .. }));
+    assert_eq!(error_kind(tracker.recv_data(3, vec![0; 4], false)), StreamErrorKind::ContentLengthMismatch { declared: 3, received: 4 });
+
+    let mut headers = response("200", "3");
+    headers.push(H2Header::new("content-length", "4"));
+    assert_eq!(error_kind(tracker.recv_headers(5, headers, false)), StreamErrorKind::InvalidContentLength);
+}
+
+#[test]
+fn test_head_response_skipped() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.send_request(1, &H2Request::new("HEAD", "https", "example.com", "/"), true, 0);
+    assert!(matches!(tracker.recv_headers(1, response("200", "1234"), true), StreamEvent::Response { .. }));
+}
+
+#[test]
+fn test_head_response_checked_when_unregistered() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    let kind = error_kind(tracker.recv_headers(1, response("200", "1234"), true));
+    assert_eq!(kind, StreamErrorKind::ContentLengthMismatch { declared: 1234, received: 0 });
+}
+
+#[test]
+fn test_204_and_304_skipped() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    assert!(matches!(tracker.recv_headers(1, response("304", "500"), true), StreamEvent::Response { .. }));
+    assert!(matches!(tracker.recv_headers(3, response("204", "7"), true), StreamEvent::Response { .. }));
+}
+
+#[test]
+fn test_connect_tunnel_skipped() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    let connect = H2Request::from_headers(vec![H2Header::new(":method", "CONNECT"), H2Header::new(":authority", "a:443")]).unwrap();
+    tracker.send_request(1, &connect, false, 0);
+    tracker.recv_headers(1, response("200", "0"), false);
+    assert!(matches!(tracker.recv_data(1, vec![0; 100], false), StreamEvent::Data { .. }));
+
+    // A failed CONNECT has ordinary content
+    tracker.send_request(3, &connect, false, 0);
+    tracker.recv_headers(3, response("403", "2"), false);
+    assert!(matches!(tracker.recv_data(3, vec![0; 3], true), StreamEvent::StreamError { .. }));
+}
+
+#[test]
+fn test_informational_length_ignored() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    assert!(matches!(tracker.recv_headers(1, response("103", "99"), false), StreamEvent::Informational { .. }));
+    tracker.recv_headers(1, response("200", "2"), false);
+    assert!(matches!(tracker.recv_data(1, vec![0; 2], true), StreamEvent::Data { .. }));
+}
+
+#[test]
+fn test_mismatch_display() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    tracker.recv_headers(1, request_with_length("1"), false);
+    match tracker.recv_data(1, vec![0; 2], false) {
+        StreamEvent::StreamError { error, .. } => {
+            assert_eq!(error.to_string(), "content-length 1 but 2 bytes of DATA received");
+        }
+        other => panic!("Expected StreamError, got {:?}", other),
+    }
+}
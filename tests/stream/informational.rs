@@ -0,0 +1,132 @@
+//! Tests for 1xx responses and Expect: 100-continue gating
+
+use h2_sans_io::{
+    ContinueState, H2Header, H2Request, Role, StreamEvent, StreamTracker, DEFAULT_CONTINUE_TIMEOUT_MS,
+};
+
+fn status(code: &str) -> Vec<H2Header> {
+    vec![H2Header::new(":status", code)]
+}
+
+fn upload(expect: bool) -> H2Request {
+    let mut req = H2Request::new("PUT", "https", "example.com", "/upload");
+    if expect {
+        req.headers.push(H2Header::new("expect", "100-continue"));
+    }
+    req
+}
+
+#[test]
+fn test_expects_continue() {
+    assert!(upload(true).expects_continue());
+    assert!(!upload(false).expects_continue());
+    let mut req = upload(false);
+    req.headers.push(H2Header::new("expect", "100-Continue"));
+    assert!(req.expects_continue());
+}
+
+#[test]
+fn test_not_expected_without_header() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.send_request(1, &upload(false), false, 0);
+    assert_eq!(tracker.continue_state(1, 0), ContinueState::NotExpected);
+    assert!(tracker.continue_state(1, 0).may_send_body());
+    assert_eq!(tracker.next_continue_deadline(), None);
+    // Unknown streams have no expectation either
+    assert_eq!(tracker.continue_state(99, 0), ContinueState::NotExpected);
+}
+
+#[test]
+fn test_not_expected_without_body() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.send_request(1, &upload(true), true, 0);
+    assert_eq!(tracker.continue_state(1, 0), ContinueState::NotExpected);
+}
+
+#[test]
+fn test_wait_then_continue() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.send_request(1, &upload(true), false, 5_000);
+    let deadline_ms = 5_000 + DEFAULT_CONTINUE_TIMEOUT_MS;
+    assert_eq!(tracker.continue_state(1, 5_000), ContinueState::Waiting { deadline_ms });
+    assert!(!tracker.continue_state(1, 5_000).may_send_body());
+    assert_eq!(tracker.next_continue_deadline(), Some(deadline_ms));
+
+    match tracker.recv_headers(1, status("100"), false) {
+        StreamEvent::Informational { response, .. } => assert_eq!(response.status, 100),
+        other => panic!("Expected Informational, got {:?}", other),
+    }
+    assert_eq!(tracker.continue_state(1, 5_001), ContinueState::Continue);
+    assert_eq!(tracker.next_continue_deadline(), None);
+
+    // The final response follows later
+    assert!(matches!(tracker.recv_headers(1, status("201"), true), StreamEvent::Response { .. }));
+    assert_eq!(tracker.continue_state(1, 5_002), ContinueState::Continue);
+}
+
+#[test]
+fn test_timeout_releases_body() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.set_continue_timeout(250);
+    tracker.send_request(1, &upload(true), false, 1_000);
+    assert_eq!(tracker.continue_state(1, 1_249), ContinueState::Waiting { deadline_ms: 1_250 });
+    assert_eq!(tracker.continue_state(1, 1_250), ContinueState::TimedOut);
+    assert!(tracker.continue_state(1, 1_250).may_send_body());
+}
+
+#[test]
+fn test_early_hints_do_not_release_body() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.send_request(1, &upload(true), false, 0);
+    match tracker.recv_headers(1, vec![H2Header::new(":status", "103"), H2Header::new("link", "</style.css>; rel=preload")], false) {
+        StreamEvent::Informational { response, .. } => {
+            assert_eq!(response.status, 103);
+            assert_eq!(response.header("link").unwrap().value, b"</style.css>; rel=preload");
+        }
+        other => panic!("Expected Informational, got {:?}", other),
+    }
+    assert_eq!(tracker.continue_state(1, 10), ContinueState::Waiting { deadline_ms: DEFAULT_CONTINUE_TIMEOUT_MS });
+}
+
+#[test]
+fn test_final_response_before_continue() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.send_request(1, &upload(true), false, 0);
+    assert!(matches!(tracker.recv_headers(1, status("413"), true), StreamEvent::Response { .. }));
+    let state = tracker.continue_state(1, 10_000);
+    assert_eq!(state, ContinueState::FinalResponse);
+    assert!(!state.may_send_body());
+}
+
+#[test]
+fn test_next_deadline_is_earliest() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.send_request(1, &upload(true), false, 500);
+    tracker.send_request(3, &upload(true), false, 100);
+    tracker.send_request(5, &upload(false), false, 0);
+    assert_eq!(tracker.next_continue_deadline(), Some(100 + DEFAULT_CONTINUE_TIMEOUT_MS));
+    tracker.remove_stream(3);
+    assert_eq!(tracker.next_continue_deadline(), Some(500 + DEFAULT_CONTINUE_TIMEOUT_MS));
+}
+
+#[test]
+fn test_multiple_interim_responses_then_final() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.send_request(1, &upload(true), false, 0);
+    let mut seen = Vec::new();
+    for code in ["103", "100", "103", "200"] {
+        match tracker.recv_headers(1, status(code), false) {
+            StreamEvent::Informational { response, .. } => seen.push(("interim", response.status)),
+            StreamEvent::Response { response, .. } => seen.push(("final", response.status)),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+    assert_eq!(seen, vec![("interim", 103), ("interim", 100), ("interim", 103), ("final", 200)]);
+    assert!(matches!(tracker.recv_data(1, b"ok".to_vec(), true), StreamEvent::Data { .. }));
+}
+
+#[test]
+#[should_panic(expected = "server-side")]
+fn test_send_request_on_server_panics() {
+    StreamTracker::new(Role::Server).send_request(1, &upload(true), false, 0);
+}
@@ -0,0 +1,5 @@
+//! Test module for stream message tracking
+
+mod content_length;
+mod informational;
+mod trailers;
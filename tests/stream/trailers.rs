@@ -0,0 +1,228 @@
+//! Tests for classifying header blocks as heads, interim responses and trailers
+
+use h2_sans_io::{
+    error_code, H2Codec, H2Event, H2Header, HeaderError, HeaderErrorKind, HpackDecoder, HpackEncoder, Role,
+    StreamErrorKind, StreamEvent, StreamTracker, ValidationProfile,
+};
+
+fn h(name: &str, value: &str) -> H2Header {
+    H2Header::new(name, value)
+}
+
+fn request_head() -> Vec<H2Header> {
+    vec![h(":method", "POST"), h(":scheme", "https"), h(":path", "/rpc"), h("content-type", "application/grpc")]
+}
+
+fn error_kind(event: StreamEvent) -> StreamErrorKind {
+    match event {
+        StreamEvent::StreamError { error, .. } => error.kind,
+        other => panic!("Expected StreamError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_server_request_data_trailers() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    match tracker.recv_headers(1, request_head(), false) {
+        StreamEvent::Request { stream_id, request, end_stream } => {
+            assert_eq!(stream_id, 1);
+            assert_eq!(request.path.as_deref(), Some("/rpc"));
+            assert!(!end_stream);
+        }
+        other => panic!("Expected Request, got {:?}", other),
+    }
+    assert_eq!(
+        tracker.recv_data(1, b"msg".to_vec(), false),
+        StreamEvent::Data { stream_id: 1, data: b"msg".to_vec(), end_stream: false }
+    );
+    assert_eq!(
+        tracker.recv_headers(1, vec![h("grpc-status", "0")], true),
+        StreamEvent::Trailers { stream_id: 1, headers: vec![h("grpc-status", "0")] }
+    );
+}
+
+#[test]
+fn test_client_response_and_trailers() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    assert_eq!(tracker.role(), Role::Client);
+    match tracker.recv_headers(1, vec![h(":status", "200")], false) {
+        StreamEvent::Response { response, end_stream, .. } => {
+            assert_eq!(response.status, 200);
+            assert!(!end_stream);
+        }
+        other => panic!("Expected Response, got {:?}", other),
+    }
+    // Trailers may directly follow the head, without DATA
+    match tracker.recv_headers(1, vec![h("grpc-status", "13"), h("grpc-message", "boom")], true) {
+        StreamEvent::Trailers { headers, .. } => assert_eq!(headers.len(), 2),
+        other => panic!("Expected Trailers, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_informational_before_final_response() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    for status in [100, 103, 103] {
+        match tracker.recv_headers(1, vec![h(":status", &status.to_string())], false) {
+            StreamEvent::Informational { response, .. } => assert_eq!(response.status, status),
+            other => panic!("Expected Informational, got {:?}", other),
+        }
+    }
+    assert!(matches!(tracker.recv_headers(1, vec![h(":status", "204")], true), StreamEvent::Response { .. }));
+}
+
+#[test]
+fn test_informational_with_end_stream_rejected() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    let kind = error_kind(tracker.recv_headers(1, vec![h(":status", "100")], true));
+    assert_eq!(kind, StreamErrorKind::InformationalEndStream);
+}
+
+#[test]
+fn test_switching_protocols_is_malformed() {
+    // RFC 9113 Section 8.6: HTTP/2 does not support 101
+    let mut tracker = StreamTracker::new(Role::Client);
+    match tracker.recv_headers(1, vec![h(":status", "101")], false) {
+        StreamEvent::StreamError { error, .. } => {
+            assert_eq!(error.kind, StreamErrorKind::Malformed(HeaderError { kind: HeaderErrorKind::InvalidPseudoValue, index: 0 }));
+            assert_eq!(error.error_code(), error_code::PROTOCOL_ERROR);
+        }
+        other => panic!("Expected StreamError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_server_never_sees_informational() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    let kind = error_kind(tracker.recv_headers(1, vec![h(":status", "100")], false));
+    assert!(matches!(kind, StreamErrorKind::Malformed(e) if e.kind == HeaderErrorKind::UnexpectedPseudo));
+}
+
+#[test]
+fn test_trailers_without_end_stream_rejected() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    tracker.recv_headers(1, request_head(), false);
+    tracker.recv_data(1, b"x".to_vec(), false);
+    let event = tracker.recv_headers(1, vec![h("grpc-status", "0")], false);
+    match &event {
+        StreamEvent::StreamError { stream_id, error } => {
+            assert_eq!(*stream_id, 1);
+            assert_eq!(error.kind, StreamErrorKind::TrailersWithoutEndStream);
+            assert_eq!(error.error_code(), error_code::PROTOCOL_ERROR);
+        }
+        other => panic!("Expected StreamError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_pseudo_header_in_trailers_rejected() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.recv_headers(1, vec![h(":status", "200")], false);
+    let kind = error_kind(tracker.recv_headers(1, vec![h("grpc-status", "0"), h(":status", "500")], true));
+    match kind {
+        StreamErrorKind::Malformed(e) => {
+            assert_eq!(e.kind, HeaderErrorKind::UnexpectedPseudo);
+            assert_eq!(e.index, 1);
+        }
+        other => panic!("Expected Malformed, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_fields_in_trailers_rejected() {
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.recv_headers(1, vec![h(":status", "200")], false);
+    let kind = error_kind(tracker.recv_headers(1, vec![h("Grpc-Status", "0")], true));
+    assert!(matches!(kind, StreamErrorKind::Malformed(e) if e.kind == HeaderErrorKind::UppercaseName));
+}
+
+#[test]
+fn test_malformed_head_rejected() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    let kind = error_kind(tracker.recv_headers(1, vec![h(":method", "GET"), h(":path", "/")], true));
+    assert!(matches!(kind, StreamErrorKind::Malformed(e) if e.kind == HeaderErrorKind::MissingPseudo(":scheme")));
+    let kind = error_kind(tracker.recv_headers(3, [request_head(), vec![h("connection", "close")]].concat(), true));
+    assert!(matches!(kind, StreamErrorKind::Malformed(e) if e.kind == HeaderErrorKind::ConnectionSpecific));
+}
+
+#[test]
+fn test_lenient_profile_applies() {
+    let mut tracker = StreamTracker::with_validation(Role::Client, ValidationProfile::Lenient);
+    let event = tracker.recv_headers(1, vec![h(":status", "200"), h("server", "x ")], true);
+    assert!(matches!(event, StreamEvent::Response { .. }));
+}
+
+#[test]
+fn test_data_before_headers_rejected() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    assert_eq!(error_kind(tracker.recv_data(1, vec![], false)), StreamErrorKind::DataBeforeHeaders);
+
+    // A client has not received the final response after only a 1xx
+    let mut tracker = StreamTracker::new(Role::Client);
+    tracker.recv_headers(1, vec![h(":status", "103")], false);
+    assert_eq!(error_kind(tracker.recv_data(1, vec![], false)), StreamErrorKind::DataBeforeHeaders);
+}
+
+#[test]
+fn test_frames_after_end_stream() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    tracker.recv_headers(1, request_head(), true);
+    let event = tracker.recv_data(1, b"late".to_vec(), false);
+    match event {
+        StreamEvent::StreamError { error, .. } => {
+            assert_eq!(error.kind, StreamErrorKind::StreamClosed);
+            assert_eq!(error.error_code(), error_code::STREAM_CLOSED);
+        }
+        other => panic!("Expected StreamError, got {:?}", other),
+    }
+    assert_eq!(error_kind(tracker.recv_headers(1, vec![h("x", "y")], true)), StreamErrorKind::StreamClosed);
+}
+
+#[test]
+fn test_stream_closed_after_error() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    tracker.recv_headers(1, vec![h(":method", "GET")], false);
+    assert_eq!(error_kind(tracker.recv_data(1, vec![], true)), StreamErrorKind::StreamClosed);
+}
+
+#[test]
+fn test_streams_are_independent_and_removable() {
+    let mut tracker = StreamTracker::new(Role::Server);
+    tracker.recv_headers(1, request_head(), false);
+    tracker.recv_headers(3, request_head(), true);
+    assert!(matches!(tracker.recv_data(1, vec![1], true), StreamEvent::Data { .. }));
+    assert_eq!(tracker.stream_count(), 2);
+    tracker.remove_stream(1);
+    tracker.remove_stream(3);
+    assert_eq!(tracker.stream_count(), 0);
+}
+
+#[test]
+fn test_with_codec_and_decoder() {
+    let mut encoder = HpackEncoder::new();
+    let mut bytes = H2Codec::create_headers_frame(1, &encoder.encode(&request_head()), false);
+    bytes.extend(H2Codec::create_data_frames(1, b"payload", false, 16_384).concat());
+    bytes.extend(H2Codec::create_headers_frame(1, &encoder.encode(&[h("grpc-status", "0")]), true));
+
+    let mut codec = H2Codec::new();
+    codec.set_preface_received(true);
+    let mut decoder = HpackDecoder::new();
+    let mut tracker = StreamTracker::new(Role::Server);
+    let mut kinds = Vec::new();
+    for event in codec.process(&bytes).unwrap() {
+        let classified = match event {
+            H2Event::Headers { stream_id, header_block, end_stream, .. } => {
+                tracker.recv_headers(stream_id, decoder.decode(&header_block).unwrap(), end_stream)
+            }
+            H2Event::Data { stream_id, data, end_stream } => tracker.recv_data(stream_id, data, end_stream),
+            other => panic!("unexpected {:?}", other),
+        };
+        kinds.push(match classified {
+            StreamEvent::Request { .. } => "request",
+            StreamEvent::Data { .. } => "data",
+            StreamEvent::Trailers { .. } => "trailers",
+            other => panic!("unexpected {:?}", other),
+        });
+    }
+    assert_eq!(kinds, vec!["request", "data", "trailers"]);
+}
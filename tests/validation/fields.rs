@@ -0,0 +1,126 @@
+//! Tests for RFC 9113 Section 8.2 field validation
+
+use h2_sans_io::{error_code, validate_headers, H2Header, HeaderErrorKind, ValidationProfile};
+
+use ValidationProfile::{Lenient, Strict};
+
+fn check(name: &[u8], value: &[u8], profile: ValidationProfile) -> Result<(), HeaderErrorKind> {
+    validate_headers(&[H2Header::new(name, value)], profile).map_err(|e| e.kind)
+}
+
+#[test]
+fn test_typical_request_passes() {
+    let headers = vec![
+        H2Header::new(":method", "GET"),
+        H2Header::new(":scheme", "https"),
+        H2Header::new(":path", "/search?q=a b"),
+        H2Header::new(":authority", "example.com"),
+        H2Header::new("accept", "text/html, */*;q=0.8"),
+        H2Header::new("x-trace-id", "abc-123"),
+        H2Header::new("te", "trailers"),
+        H2Header::new("cookie", ""),
+    ];
+    assert_eq!(validate_headers(&headers, Strict), Ok(()));
+    assert_eq!(validate_headers(&headers, Lenient), Ok(()));
+    assert_eq!(ValidationProfile::default(), Strict);
+}
+
+#[test]
+fn test_uppercase_name_rejected() {
+    for profile in [Strict, Lenient] {
+        assert_eq!(check(b"Content-Type", b"text/plain", profile), Err(HeaderErrorKind::UppercaseName));
+        assert_eq!(check(b":Path", b"/", profile), Err(HeaderErrorKind::UppercaseName));
+    }
+}
+
+#[test]
+fn test_empty_name_rejected() {
+    assert_eq!(check(b"", b"x", Lenient), Err(HeaderErrorKind::EmptyName));
+    assert_eq!(check(b":", b"x", Lenient), Err(HeaderErrorKind::EmptyName));
+}
+
+#[test]
+fn test_forbidden_name_bytes() {
+    for b in [0x00, b'\t', b' ', 0x7F, 0x80, 0xFF] {
+        let name = [b'x', b, b'y'];
+        for profile in [Strict, Lenient] {
+            assert_eq!(check(&name, b"v", profile), Err(HeaderErrorKind::InvalidNameByte(b)), "byte {:#x}", b);
+        }
+    }
+}
+
+#[test]
+fn test_colon_only_leading() {
+    assert_eq!(check(b"x:y", b"v", Lenient), Err(HeaderErrorKind::InvalidNameByte(b':')));
+    assert_eq!(check(b"::path", b"/", Lenient), Err(HeaderErrorKind::InvalidNameByte(b':')));
+    assert_eq!(check(b":path", b"/", Strict), Ok(()));
+}
+
+#[test]
+fn test_non_token_name_strict_only() {
+    assert_eq!(check(b"x(y)", b"v", Strict), Err(HeaderErrorKind::InvalidNameByte(b'(')));
+    assert_eq!(check(b"x(y)", b"v", Lenient), Ok(()));
+    assert_eq!(check(b"x-a_b.c~d!", b"v", Strict), Ok(()));
+}
+
+#[test]
+fn test_value_cr_lf_nul_rejected() {
+    for b in [0x00, b'\r', b'\n'] {
+        let value = [b'a', b, b'b'];
+        for profile in [Strict, Lenient] {
+            assert_eq!(check(b"x", &value, profile), Err(HeaderErrorKind::InvalidValueByte(b)));
+        }
+    }
+}
+
+#[test]
+fn test_value_control_bytes_strict_only() {
+    assert_eq!(check(b"x", b"a\x01b", Strict), Err(HeaderErrorKind::InvalidValueByte(0x01)));
+    assert_eq!(check(b"x", b"a\x7Fb", Strict), Err(HeaderErrorKind::InvalidValueByte(0x7F)));
+    assert_eq!(check(b"x", b"a\x01b", Lenient), Ok(()));
+    // HTAB and obs-text are allowed inside values
+    assert_eq!(check(b"x", b"a\tb\xE9", Strict), Ok(()));
+}
+
+#[test]
+fn test_surrounding_whitespace() {
+    for value in [&b" a"[..], b"a ", b"\ta", b"a\t", b" "] {
+        assert_eq!(check(b"x", value, Strict), Err(HeaderErrorKind::SurroundingWhitespace));
+        assert_eq!(check(b"x", value, Lenient), Ok(()));
+    }
+    assert_eq!(check(b"x", b"a b", Strict), Ok(()));
+}
+
+#[test]
+fn test_connection_specific_rejected() {
+    for name in ["connection", "proxy-connection", "keep-alive", "transfer-encoding", "upgrade"] {
+        for profile in [Strict, Lenient] {
+            assert_eq!(check(name.as_bytes(), b"x", profile), Err(HeaderErrorKind::ConnectionSpecific), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_te_only_trailers() {
+    assert_eq!(check(b"te", b"trailers", Strict), Ok(()));
+    assert_eq!(check(b"te", b"Trailers", Lenient), Ok(()));
+    assert_eq!(check(b"te", b"gzip", Lenient), Err(HeaderErrorKind::InvalidTe));
+    assert_eq!(check(b"te", b"trailers, gzip", Strict), Err(HeaderErrorKind::InvalidTe));
+}
+
+#[test]
+fn test_error_reports_first_offending_index() {
+    let headers = vec![
+        H2Header::new(":method", "GET"),
+        H2Header::new("ok", "1"),
+        H2Header::new("connection", "close"),
+        H2Header::new("Bad", "2"),
+    ];
+    let err = validate_headers(&headers, Strict).unwrap_err();
+    assert_eq!(err.index, 2);
+    assert_eq!(err.kind, HeaderErrorKind::ConnectionSpecific);
+    assert_eq!(err.error_code(), error_code::PROTOCOL_ERROR);
+    let msg = err.to_string();
+    assert!(msg.contains("field 2"));
+    assert!(msg.contains("connection-specific"));
+}
@@ -0,0 +1,206 @@
+//! Tests for H2Request / H2Response pseudo-header handling
+
+use h2_sans_io::{H2Header, H2Request, H2Response, HeaderErrorKind, HpackDecoder, HpackEncoder};
+
+fn h(name: &str, value: &str) -> H2Header {
+    H2Header::new(name, value)
+}
+
+fn request_err(headers: Vec<H2Header>) -> (HeaderErrorKind, usize) {
+    let err = H2Request::from_headers(headers).unwrap_err();
+    (err.kind, err.index)
+}
+
+fn response_err(headers: Vec<H2Header>) -> (HeaderErrorKind, usize) {
+    let err = H2Response::from_headers(headers).unwrap_err();
+    (err.kind, err.index)
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Requests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_regular_request() {
+    let req = H2Request::from_headers(vec![
+        h(":method", "GET"),
+        h(":scheme", "https"),
+        h(":authority", "example.com"),
+        h(":path", "/index.html"),
+        h("accept", "*/*"),
+        h("accept", "text/html"),
+    ])
+    .unwrap();
+    assert_eq!(req.method, "GET");
+    assert_eq!(req.scheme.as_deref(), Some("https"));
+    assert_eq!(req.authority.as_deref(), Some("example.com"));
+    assert_eq!(req.path.as_deref(), Some("/index.html"));
+    assert_eq!(req.protocol, None);
+    assert_eq!(req.headers.len(), 2);
+    assert_eq!(req.header("accept").unwrap().value, b"*/*");
+    assert!(!req.is_connect());
+}
+
+#[test]
+fn test_authority_optional_for_regular_request() {
+    let req = H2Request::from_headers(vec![h(":method", "GET"), h(":scheme", "https"), h(":path", "*")]).unwrap();
+    assert_eq!(req.authority, None);
+}
+
+#[test]
+fn test_pseudo_order_among_themselves_is_free() {
+    let req = H2Request::from_headers(vec![h(":path", "/"), h(":scheme", "http"), h(":method", "GET")]).unwrap();
+    assert_eq!(req.method, "GET");
+}
+
+#[test]
+fn test_missing_required_pseudo() {
+    assert_eq!(request_err(vec![h(":scheme", "https"), h(":path", "/")]), (HeaderErrorKind::MissingPseudo(":method"), 2));
+    assert_eq!(request_err(vec![h(":method", "GET"), h(":path", "/")]), (HeaderErrorKind::MissingPseudo(":scheme"), 2));
+    assert_eq!(request_err(vec![h(":method", "GET"), h(":scheme", "https")]), (HeaderErrorKind::MissingPseudo(":path"), 2));
+}
+
+#[test]
+fn test_empty_path_rejected() {
+    let err = request_err(vec![h(":method", "GET"), h(":scheme", "https"), h(":path", "")]);
+    assert_eq!(err, (HeaderErrorKind::InvalidPseudoValue, 2));
+}
+
+#[test]
+fn test_pseudo_after_regular() {
+    let err = request_err(vec![h(":method", "GET"), h("accept", "*/*"), h(":scheme", "https"), h(":path", "/")]);
+    assert_eq!(err, (HeaderErrorKind::PseudoAfterRegular, 2));
+}
+
+#[test]
+fn test_duplicate_pseudo() {
+    let err = request_err(vec![h(":method", "GET"), h(":method", "POST"), h(":scheme", "https"), h(":path", "/")]);
+    assert_eq!(err, (HeaderErrorKind::DuplicatePseudo, 1));
+}
+
+#[test]
+fn test_response_pseudo_in_request() {
+    let err = request_err(vec![h(":method", "GET"), h(":status", "200")]);
+    assert_eq!(err, (HeaderErrorKind::UnexpectedPseudo, 1));
+    let err = request_err(vec![h(":foo", "x")]);
+    assert_eq!(err, (HeaderErrorKind::UnexpectedPseudo, 0));
+}
+
+#[test]
+fn test_connect_request() {
+    let req = H2Request::from_headers(vec![h(":method", "CONNECT"), h(":authority", "example.com:443")]).unwrap();
+    assert!(req.is_connect());
+    assert!(!req.is_extended_connect());
+    assert_eq!(req.scheme, None);
+    assert_eq!(req.path, None);
+}
+
+#[test]
+fn test_connect_rules() {
+    assert_eq!(request_err(vec![h(":method", "CONNECT")]), (HeaderErrorKind::MissingPseudo(":authority"), 1));
+    let err = request_err(vec![h(":method", "CONNECT"), h(":authority", "a:443"), h(":path", "/")]);
+    assert_eq!(err, (HeaderErrorKind::UnexpectedPseudo, 2));
+    let err = request_err(vec![h(":method", "CONNECT"), h(":scheme", "https"), h(":authority", "a:443")]);
+    assert_eq!(err, (HeaderErrorKind::UnexpectedPseudo, 1));
+}
+
+#[test]
+fn test_extended_connect_request() {
+    let req = H2Request::from_headers(vec![
+        h(":method", "CONNECT"),
+        h(":protocol", "websocket"),
+        h(":scheme", "https"),
+        h(":path", "/chat"),
+        h(":authority", "example.com"),
+        h("sec-websocket-version", "13"),
+    ])
+    .unwrap();
+    assert!(req.is_extended_connect());
+    assert_eq!(req.protocol.as_deref(), Some("websocket"));
+}
+
+#[test]
+fn test_extended_connect_rules() {
+    let err = request_err(vec![h(":method", "CONNECT"), h(":protocol", "websocket"), h(":scheme", "https"), h(":path", "/")]);
+    assert_eq!(err, (HeaderErrorKind::MissingPseudo(":authority"), 4));
+    let err = request_err(vec![h(":method", "CONNECT"), h(":protocol", "websocket"), h(":authority", "a")]);
+    assert_eq!(err, (HeaderErrorKind::MissingPseudo(":scheme"), 3));
+    // :protocol is only valid with CONNECT
+    let err = request_err(vec![h(":method", "GET"), h(":protocol", "websocket"), h(":scheme", "https"), h(":path", "/")]);
+    assert_eq!(err, (HeaderErrorKind::UnexpectedPseudo, 1));
+}
+
+#[test]
+fn test_request_into_headers_order() {
+    let mut req = H2Request::new("POST", "https", "example.com", "/upload");
+    req.headers.push(h("content-type", "text/plain"));
+    let headers = req.clone().into_headers();
+    let names: Vec<&[u8]> = headers.iter().map(|h| h.name.as_slice()).collect();
+    assert_eq!(names, vec![&b":method"[..], b":scheme", b":authority", b":path", b"content-type"]);
+    assert_eq!(H2Request::from_headers(headers).unwrap(), req);
+}
+
+#[test]
+fn test_connect_into_headers_omits_absent() {
+    let req = H2Request::from_headers(vec![h(":authority", "a:443"), h(":method", "CONNECT")]).unwrap();
+    assert_eq!(req.into_headers(), vec![h(":method", "CONNECT"), h(":authority", "a:443")]);
+}
+
+#[test]
+fn test_request_hpack_roundtrip() {
+    let req = H2Request::new("GET", "https", "example.com", "/");
+    let block = HpackEncoder::new().encode(&req.clone().into_headers());
+    let decoded = HpackDecoder::new().decode(&block).unwrap();
+    assert_eq!(H2Request::from_headers(decoded).unwrap(), req);
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Responses
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_response() {
+    let resp = H2Response::from_headers(vec![h(":status", "404"), h("content-length", "0")]).unwrap();
+    assert_eq!(resp.status, 404);
+    assert_eq!(resp.header("content-length").unwrap().value, b"0");
+    assert!(!resp.is_informational());
+    assert!(H2Response::new(103).is_informational());
+}
+
+#[test]
+fn test_response_errors() {
+    assert_eq!(response_err(vec![h("server", "x")]), (HeaderErrorKind::MissingPseudo(":status"), 1));
+    assert_eq!(response_err(vec![h(":status", "200"), h(":status", "204")]), (HeaderErrorKind::DuplicatePseudo, 1));
+    assert_eq!(response_err(vec![h(":status", "200"), h(":path", "/")]), (HeaderErrorKind::UnexpectedPseudo, 1));
+    assert_eq!(response_err(vec![h("server", "x"), h(":status", "200")]), (HeaderErrorKind::PseudoAfterRegular, 1));
+    for bad in ["20", "2000", "abc", "099", " 200", ""] {
+        assert_eq!(response_err(vec![h(":status", bad)]), (HeaderErrorKind::InvalidPseudoValue, 0), "{:?}", bad);
+    }
+}
+
+#[test]
+fn test_response_into_headers() {
+    let mut resp = H2Response::new(200);
+    resp.headers.push(h("content-type", "text/html"));
+    let headers = resp.clone().into_headers();
+    assert_eq!(headers, vec![h(":status", "200"), h("content-type", "text/html")]);
+    assert_eq!(H2Response::from_headers(headers).unwrap(), resp);
+}
+
+#[test]
+#[should_panic(expected = "three digits")]
+fn test_response_new_rejects_bad_status() {
+    H2Response::new(42);
+}
+
+#[test]
+#[should_panic(expected = "Switching Protocols")]
+fn test_response_new_rejects_switching_protocols() {
+    H2Response::new(101);
+}
+
+#[test]
+fn test_missing_display() {
+    let err = H2Response::from_headers(vec![]).unwrap_err();
+    assert_eq!(err.to_string(), "malformed header list: missing :status pseudo-header");
+}
@@ -0,0 +1,4 @@
+//! Test module for header validation
+
+mod fields;
+mod messages;